@@ -0,0 +1,21 @@
+//! The HTTP client on its own, without any Discord library or web framework.
+//!
+//! This only needs the `api` feature:
+//!
+//! ```toml
+//! topgg = { version = "2.0", default-features = false, features = ["api"] }
+//! ```
+
+use topgg::{Client, Stats};
+
+#[tokio::main(flavor = "current_thread")]
+async fn main() -> topgg::Result<()> {
+  let client = Client::new(std::env::var("TOPGG_TOKEN").expect("TOPGG_TOKEN is not set."));
+
+  client.post_stats(Stats::from(12345)).await?;
+
+  let bot = client.get_bot(264811613708746752).await?;
+  println!("{} has {} monthly votes.", bot.username, bot.monthly_votes);
+
+  Ok(())
+}
@@ -1,4 +1,4 @@
-use crate::InnerClient;
+use crate::{Client, InnerClient};
 use std::sync::Arc;
 
 pub trait AsClientSealed {
@@ -7,7 +7,7 @@ pub trait AsClientSealed {
 
 /// A private trait that represents any datatype that can be interpreted as a [Top.gg API](https://docs.top.gg) Client.
 ///
-/// This can either be a reference to an existing [`Client`][crate::Client] or a [`&str`][std::str] representing a [Top.gg API](https://docs.top.gg) token.
+/// This can either be a reference to an existing [`Client`][crate::Client], an [`Arc<Client>`][Arc] or a [`&str`][std::str] representing a [Top.gg API](https://docs.top.gg) token.
 pub trait AsClient: AsClientSealed {}
 
 impl AsClientSealed for str {
@@ -18,3 +18,71 @@ impl AsClientSealed for str {
 }
 
 impl AsClient for str {}
+
+impl AsClientSealed for Arc<Client> {
+  #[inline(always)]
+  fn as_client(&self) -> Arc<InnerClient> {
+    (**self).as_client()
+  }
+}
+
+impl AsClient for Arc<Client> {}
+
+impl<T> AsClientSealed for &T
+where
+  T: AsClientSealed + ?Sized,
+{
+  #[inline(always)]
+  fn as_client(&self) -> Arc<InnerClient> {
+    (**self).as_client()
+  }
+}
+
+impl<T> AsClient for &T where T: AsClient + ?Sized {}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::autoposter::{Autoposter, Handler, SharedStats, MIN_AUTOPOST_INTERVAL};
+
+  #[test]
+  fn shared_clients_are_not_copied() {
+    let client = Arc::new(Client::new(String::from("token")));
+
+    assert!(Arc::ptr_eq(&client.as_client(), &(*client).as_client()));
+  }
+
+  struct Stub {
+    client: Arc<InnerClient>,
+    stats: SharedStats,
+  }
+
+  impl Stub {
+    fn new<C>(client: &C) -> Self
+    where
+      C: AsClient,
+    {
+      Self {
+        client: client.as_client(),
+        stats: SharedStats::new(),
+      }
+    }
+  }
+
+  impl Handler for Stub {
+    fn stats(&self) -> &SharedStats {
+      &self.stats
+    }
+  }
+
+  #[test]
+  fn autoposters_accept_shared_clients() {
+    let client = Arc::new(Client::new(String::from("token")));
+    let autoposter = Autoposter::new(Stub::new(&client), MIN_AUTOPOST_INTERVAL);
+
+    assert!(Arc::ptr_eq(&autoposter.client, &(*client).as_client()));
+
+    // and token strings.
+    let _ = Stub::new(&"token");
+  }
+}
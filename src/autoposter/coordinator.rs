@@ -0,0 +1,30 @@
+/// An async trait for coordinating multiple processes of the same bot, so that only one of them posts its statistics per interval, e.g. through a distributed lock in Redis.
+///
+/// The built-in [`Handler`][crate::autoposter::Handler]s call [`try_acquire`][PostCoordinator::try_acquire] right before posting, and skip posting if it returns `false`. The process that acquired it should post the statistics of the whole bot, e.g. ones [merged][crate::Stats::merge] from every shard.
+///
+/// It's described as follows (without [`async_trait`]'s macro expansion):
+/// ```rust,no_run
+/// #[async_trait::async_trait]
+/// pub trait PostCoordinator: Send + Sync + 'static {
+///   async fn try_acquire(&self) -> bool;
+/// }
+/// ```
+#[async_trait::async_trait]
+pub trait PostCoordinator: Send + Sync + 'static {
+  /// Attempts to acquire the right to post the bot's statistics for the current interval, returning `true` if this process should post them.
+  ///
+  /// A distributed lock should expire on its own after around an interval, so that another process can take over if this one dies.
+  async fn try_acquire(&self) -> bool;
+}
+
+/// A [`PostCoordinator`] that always lets this process post, which is the default for a single process.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct NoCoordinator;
+
+#[async_trait::async_trait]
+impl PostCoordinator for NoCoordinator {
+  #[inline(always)]
+  async fn try_acquire(&self) -> bool {
+    true
+  }
+}
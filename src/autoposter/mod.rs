@@ -1,15 +1,30 @@
 use crate::Stats;
 use core::{
+  future::Future,
   ops::{Deref, DerefMut},
   time::Duration,
 };
-use std::sync::Arc;
+use futures_util::future::BoxFuture;
+use std::sync::{Arc, Mutex as SyncMutex, Weak};
 use tokio::sync::{RwLock, RwLockWriteGuard};
 
 mod client;
+mod coordinator;
 
 pub use client::AsClient;
 pub(crate) use client::AsClientSealed;
+pub use coordinator::{NoCoordinator, PostCoordinator};
+
+cfg_if::cfg_if! {
+  if #[cfg(any(feature = "serenity", feature = "twilight"))] {
+    mod poster;
+
+    pub(crate) use poster::{BuiltinHandler, GuildEvent, Poster};
+
+    #[cfg_attr(docsrs, doc(cfg(any(feature = "serenity", feature = "twilight"))))]
+    pub use poster::{ChangeThreshold, PostMode};
+  }
+}
 
 cfg_if::cfg_if! {
   if #[cfg(feature = "serenity")] {
@@ -25,13 +40,114 @@ cfg_if::cfg_if! {
     mod twilight_impl;
 
     #[cfg_attr(docsrs, doc(cfg(feature = "twilight")))]
-    pub use twilight_impl::Twilight;
+    pub use twilight_impl::{PostTriggers, Twilight};
   }
 }
 
+cfg_if::cfg_if! {
+  if #[cfg(any(feature = "serenity", feature = "twilight"))] {
+    impl<H> Autoposter<H>
+    where
+      H: Handler + BuiltinHandler,
+    {
+      /// Whether the bot's statistics were successfully posted recently, e.g. for a readiness probe.
+      ///
+      /// This returns `false` if the last successful post is older than the autoposter's interval plus `max_age`, so that it doesn't false-alarm between posts. Before the first successful post, the time is measured from the creation of this [`Autoposter`] instead.
+      #[inline(always)]
+      pub async fn is_healthy(&self, max_age: Duration) -> bool {
+        self.handler.poster().is_healthy(max_age).await
+      }
+
+      /// The approximate total amount of members across the bot's guilds, summed from the member counts carried by guild create and guild update events. [`None`] if no event carried one yet.
+      ///
+      /// This is only tracked locally, as [Top.gg](https://top.gg)'s API doesn't accept a member count in the posted statistics. Member counts are not tracked with the `serenity-cached` feature, which relies on [serenity]'s own cache instead.
+      #[inline(always)]
+      pub async fn approximate_member_count(&self) -> Option<u64> {
+        self.handler.poster().approximate_member_count().await
+      }
+
+      /// Starts shutting down, ignoring every guild event from now on. Discord may remove every guild from the bot while the gateway closes, which would otherwise wipe the server count right before the final post of [`shutdown`][Autoposter::shutdown].
+      #[inline(always)]
+      pub fn begin_shutdown(&self) {
+        self.handler.poster().begin_shutdown();
+      }
+
+      /// Shuts down, ignoring every guild event from now on and posting the current statistics one last time regardless of the interval. Nothing is posted afterwards, and calling this again does nothing.
+      ///
+      /// Call [`begin_shutdown`][Autoposter::begin_shutdown] before closing the gateway connection, then this once it's closed.
+      ///
+      /// # Panics
+      ///
+      /// Panics if the client uses an invalid [Top.gg API](https://docs.top.gg) token (unauthorized)
+      ///
+      /// # Errors
+      ///
+      /// Errors if any of the following conditions are met:
+      /// - An internal error from the client itself preventing it from sending a HTTP request to [Top.gg](https://top.gg) ([`InternalClientError`][crate::Error::InternalClientError])
+      /// - An unexpected response from the [Top.gg](https://top.gg) servers ([`InternalServerError`][crate::Error::InternalServerError])
+      /// - The client is being ratelimited from sending more HTTP requests ([`Ratelimit`][crate::Error::Ratelimit])
+      /// - The token isn't allowed to post the statistics of this bot ([`Forbidden`][crate::Error::Forbidden])
+      /// - The server count is zero while [`reject_zero_server_count`][crate::ClientBuilder::reject_zero_server_count] is enabled ([`SuspiciousStats`][crate::Error::SuspiciousStats])
+      /// - The final post took longer than the [`shutdown timeout`][Autoposter::with_shutdown_timeout] ([`Timeout`][crate::Error::Timeout])
+      #[must_use = "a failed final post goes unnoticed unless this is handled"]
+      #[inline(always)]
+      pub async fn shutdown(&self) -> crate::Result<()> {
+        self.handler.poster().shutdown().await
+      }
+
+      /// Bounds the final post of [`shutdown`][Autoposter::shutdown] to `timeout`, so that an unresponsive [Top.gg](https://top.gg) doesn't hold back the process exit, e.g. past the grace period of a container orchestrator. Unbounded by default.
+      #[inline(always)]
+      pub fn with_shutdown_timeout(self, timeout: Duration) -> Self {
+        self.handler.poster().set_shutdown_timeout(timeout);
+        self
+      }
+
+      /// Does everything but actually sending the statistics to [Top.gg](https://top.gg) when enabled, logging what would have been posted instead (as a debug event with the `tracing` feature), e.g. to check that guild events are wired up in a staging environment without touching the bot's listing. Defaults to `false`.
+      ///
+      /// Dry-run attempts count as successful posts, including for [`is_healthy`][Autoposter::is_healthy] and the final post of [`shutdown`][Autoposter::shutdown]. [`Handler::on_post`] can still tell them apart through [`PostOutcome::dry_run`].
+      #[inline(always)]
+      pub fn with_dry_run(self, dry_run: bool) -> Self {
+        self.handler.poster().set_dry_run(dry_run);
+        self
+      }
+
+      /// Coordinates posting with other processes of the same bot through a [`PostCoordinator`], which decides whether this process posts or not. Defaults to [`NoCoordinator`].
+      ///
+      /// **NOTE:** [`is_healthy`][Autoposter::is_healthy] only considers the posts of this process, so processes that don't get to post are reported as unhealthy.
+      #[inline(always)]
+      pub fn with_coordinator<C>(self, coordinator: C) -> Self
+      where
+        C: PostCoordinator,
+      {
+        self.handler.poster().set_coordinator(Arc::new(coordinator));
+        self
+      }
+    }
+  }
+}
+
+/// The shortest interval allowed between two [`Autoposter`] posts, which is 15 minutes.
+pub const MIN_AUTOPOST_INTERVAL: Duration = Duration::from_secs(900);
+
+/// What a successful attempt to post the bot's statistics did. (See [`PostResult`])
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct PostOutcome {
+  /// Whether the statistics were only logged instead of being sent to [Top.gg](https://top.gg), as the [`Autoposter`] runs in dry-run mode.
+  pub dry_run: bool,
+}
+
+/// The outcome of an attempt to post the bot's statistics. (See [`Handler::on_post`])
+pub type PostResult = crate::Result<PostOutcome>;
+
+type PostHook = dyn for<'a> Fn(&'a PostResult) -> BoxFuture<'a, ()> + Send + Sync;
+type ChangeHook = dyn Fn(usize, usize) -> BoxFuture<'static, ()> + Send + Sync;
+
 /// A struct representing a thread-safe form of the [`Stats`] struct to be used in autoposter [`Handler`]s.
 pub struct SharedStats {
   stats: RwLock<Stats>,
+  // forwards post results to the handler of the autoposter these stats belong to.
+  post_hook: SyncMutex<Option<Arc<PostHook>>>,
+  change_hook: SyncMutex<Option<Arc<ChangeHook>>>,
 }
 
 /// A guard wrapping over tokio's [`RwLockWriteGuard`] that lets you freely feed new [`Stats`] data before being sent to the [`Autoposter`].
@@ -58,6 +174,20 @@ impl SharedStatsGuard<'_> {
   pub fn set_shard_count(&mut self, shard_count: usize) {
     self.guard.shard_count = Some(shard_count);
   }
+
+  /// Sets the current [`Stats`] shard ID and shard count, for a process posting the statistics of a single shard.
+  ///
+  /// # Panics
+  ///
+  /// Panics if the shard ID is not lower than the shard count, as [Top.gg](https://top.gg) would reject every post.
+  pub fn set_shard(&mut self, shard_id: usize, shard_count: usize) {
+    assert!(
+      shard_id < shard_count,
+      "The shard ID must be lower than the shard count."
+    );
+
+    self.guard.set_shard(shard_id, shard_count);
+  }
 }
 
 impl Deref for SharedStatsGuard<'_> {
@@ -77,11 +207,61 @@ impl DerefMut for SharedStatsGuard<'_> {
 }
 
 impl SharedStats {
-  /// Creates a new [`SharedStats`] struct. Before any modifications, the [`Stats`] struct inside defaults to zero server count.
+  /// Creates a new [`SharedStats`] struct. Before any modifications, the [`Stats`] struct inside has no server count, and the [`Autoposter`] won't post it.
   #[inline(always)]
   pub fn new() -> Self {
     Self {
-      stats: RwLock::new(Stats::from(0)),
+      stats: RwLock::new(Stats::empty()),
+      post_hook: SyncMutex::new(None),
+      change_hook: SyncMutex::new(None),
+    }
+  }
+
+  // lets every post of these stats reach `handler`, without keeping it alive.
+  fn set_post_hook<H>(&self, handler: Weak<H>)
+  where
+    H: Handler,
+  {
+    let post_handler = Weak::clone(&handler);
+    let hook: Arc<PostHook> = Arc::new(move |result| {
+      let handler = Weak::clone(&post_handler);
+
+      Box::pin(async move {
+        if let Some(handler) = handler.upgrade() {
+          handler.on_post(result).await;
+        }
+      })
+    });
+
+    let change_hook: Arc<ChangeHook> = Arc::new(move |old, new| {
+      let handler = Weak::clone(&handler);
+
+      Box::pin(async move {
+        if let Some(handler) = handler.upgrade() {
+          handler.on_significant_change(old, new).await;
+        }
+      })
+    });
+
+    *self.post_hook.lock().unwrap() = Some(hook);
+    *self.change_hook.lock().unwrap() = Some(change_hook);
+  }
+
+  #[cfg(any(feature = "serenity", feature = "twilight"))]
+  pub(crate) async fn notify_post(&self, result: &PostResult) {
+    let hook = self.post_hook.lock().unwrap().clone();
+
+    if let Some(hook) = hook {
+      hook(result).await;
+    }
+  }
+
+  #[cfg(any(feature = "serenity", feature = "twilight"))]
+  pub(crate) async fn notify_significant_change(&self, old: usize, new: usize) {
+    let hook = self.change_hook.lock().unwrap().clone();
+
+    if let Some(hook) = hook {
+      hook(old, new).await;
     }
   }
 
@@ -97,17 +277,43 @@ impl SharedStats {
 /// A trait for handling events from third-party Discord Bot libraries.
 ///
 /// The struct implementing this trait should own an [`SharedStats`] struct and update it accordingly whenever Discord updates them with new data regarding guild/shard count.
+///
+/// **NOTE:** As [`on_post`][Handler::on_post] is an `async fn`, this trait is not object safe. Use a generic `H: Handler` bound instead of `dyn Handler`.
 pub trait Handler: Send + Sync + 'static {
+  /// The name of the Discord Bot library this [`Handler`] integrates with, e.g. for diagnostics logging which integration is active. `"serenity"` and `"twilight"` for the built-in ones, `"custom"` by default. (See [`Autoposter::handler_kind`])
+  const KIND: &'static str = "custom";
+
   /// The method that borrows [`SharedStats`] to the [`Autoposter`].
   fn stats(&self) -> &SharedStats;
+
+  /// Called after every attempt of the [`Autoposter`] to post the statistics borrowed by [`stats`][Handler::stats], e.g. to record metrics. Does nothing by default. The final post of a shutdown is not included, as its result is returned to the caller instead.
+  ///
+  /// This also works for your own [`Handler`] embedding a built-in one, as long as [`stats`][Handler::stats] borrows the built-in handler's statistics.
+  fn on_post(&self, result: &PostResult) -> impl Future<Output = ()> + Send {
+    let _ = result;
+
+    async {}
+  }
+
+  /// Called after a successful post whose server count differs from the previous successful post's by more than the threshold given to the built-in handlers' `with_change_threshold`, e.g. to alert on mass joins or on a bug halving the count. Does nothing by default.
+  fn on_significant_change(&self, old: usize, new: usize) -> impl Future<Output = ()> + Send {
+    let _ = (old, new);
+
+    async {}
+  }
 }
 
 /// A struct that lets you automate the process of posting bot statistics to [Top.gg](https://top.gg) on guild events with a minimum interval.
 ///
 /// **NOTE:** This struct provides a handler that posts statistics when the bot joins or leaves guilds, ensuring at least the minimum interval between posts.
+///
+/// The built-in [`Handler`]s post right away once the statistics are first available, e.g. on the ready event, instead of waiting for the interval to pass. Afterwards, they also post once per interval even without any guild events, so bots whose guild count rarely changes don't go a long time without posting (see [`PostMode`]).
+///
+/// Their background timer task is aborted once the [`Autoposter`] and every clone of its [`handler`][Autoposter::handler] are dropped. Call [`shutdown`][Autoposter::shutdown] beforehand to post the latest statistics one last time.
 #[must_use]
 pub struct Autoposter<H> {
   handler: Arc<H>,
+  interval: Duration,
 }
 
 impl<H> Autoposter<H>
@@ -120,16 +326,17 @@ where
   ///
   /// # Panics
   ///
-  /// Panics if the interval argument is shorter than 15 minutes (900 seconds).
+  /// Panics if the interval argument is shorter than [`MIN_AUTOPOST_INTERVAL`] (15 minutes).
   pub fn new(handler: H, interval: Duration) -> Self {
     assert!(
-      interval.as_secs() >= 900,
+      interval >= MIN_AUTOPOST_INTERVAL,
       "The interval mustn't be shorter than 15 minutes."
     );
 
     let handler = Arc::new(handler);
+    handler.stats().set_post_hook(Arc::downgrade(&handler));
 
-    Self { handler }
+    Self { handler, interval }
   }
 
   /// Retrieves the [`Handler`] inside in the form of a [cloned][Arc::clone] [`Arc<H>`][Arc].
@@ -137,6 +344,31 @@ where
   pub fn handler(&self) -> Arc<H> {
     Arc::clone(&self.handler)
   }
+
+  /// The minimum interval between two posts this [`Autoposter`] was created with, e.g. for estimating when the next post could happen.
+  #[must_use]
+  #[inline(always)]
+  pub const fn interval(&self) -> Duration {
+    self.interval
+  }
+
+  /// The [`KIND`][Handler::KIND] of this [`Autoposter`]'s [`Handler`], e.g. `"serenity"`.
+  #[must_use]
+  #[inline(always)]
+  pub const fn handler_kind(&self) -> &'static str {
+    H::KIND
+  }
+}
+
+/// Cheaply clones the [`Autoposter`], with both copies sharing the same [`Handler`].
+impl<H> Clone for Autoposter<H> {
+  #[inline(always)]
+  fn clone(&self) -> Self {
+    Self {
+      handler: Arc::clone(&self.handler),
+      interval: self.interval,
+    }
+  }
 }
 
 impl<H> Deref for Autoposter<H> {
@@ -157,7 +389,7 @@ impl Autoposter<Serenity> {
   ///
   /// # Panics
   ///
-  /// Panics if the interval argument is shorter than 15 minutes (900 seconds).
+  /// Panics if the interval argument is shorter than [`MIN_AUTOPOST_INTERVAL`] (15 minutes).
   #[inline(always)]
   pub fn serenity<C>(client: &C, interval: Duration) -> Self
   where
@@ -177,7 +409,7 @@ impl Autoposter<Twilight> {
   ///
   /// # Panics
   ///
-  /// Panics if the interval argument is shorter than 15 minutes (900 seconds).
+  /// Panics if the interval argument is shorter than [`MIN_AUTOPOST_INTERVAL`] (15 minutes).
   #[inline(always)]
   pub fn twilight<C>(client: &C, interval: Duration) -> Self
   where
@@ -187,3 +419,65 @@ impl Autoposter<Twilight> {
     Self::new(Twilight::new(Arc::clone(&c), interval), interval)
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  struct Stub(SharedStats);
+
+  impl Handler for Stub {
+    fn stats(&self) -> &SharedStats {
+      &self.0
+    }
+  }
+
+  #[tokio::test]
+  async fn clones_share_the_handler() {
+    let autoposter = Autoposter::new(Stub(SharedStats::new()), MIN_AUTOPOST_INTERVAL);
+    let clone = autoposter.clone();
+
+    assert!(Arc::ptr_eq(&autoposter.handler(), &clone.handler()));
+    assert_eq!(autoposter.handler_kind(), "custom");
+
+    clone.stats().write().await.set_server_count(42);
+    assert_eq!(
+      autoposter.stats().stats.read().await.server_count(),
+      Some(42)
+    );
+  }
+
+  #[test]
+  #[should_panic(expected = "The shard ID must be lower than the shard count.")]
+  fn rejects_shard_ids_out_of_range() {
+    SharedStats::new()
+      .stats
+      .try_write()
+      .map(|guard| SharedStatsGuard { guard })
+      .unwrap()
+      .set_shard(2, 2);
+  }
+
+  #[test]
+  fn interval() {
+    let interval = Duration::from_secs(1800);
+    let autoposter = Autoposter::new(Stub(SharedStats::new()), interval);
+
+    assert_eq!(autoposter.interval(), interval);
+    assert_eq!(autoposter.clone().interval(), interval);
+  }
+
+  #[test]
+  fn accepts_the_minimum_interval() {
+    let _ = Autoposter::new(Stub(SharedStats::new()), MIN_AUTOPOST_INTERVAL);
+  }
+
+  #[test]
+  #[should_panic(expected = "The interval mustn't be shorter than 15 minutes.")]
+  fn rejects_intervals_below_the_minimum() {
+    let _ = Autoposter::new(
+      Stub(SharedStats::new()),
+      MIN_AUTOPOST_INTERVAL - Duration::from_secs(1),
+    );
+  }
+}
@@ -0,0 +1,1432 @@
+use crate::{
+  autoposter::{
+    NoCoordinator, PostCoordinator, PostOutcome, PostResult, SharedStats, SharedStatsGuard,
+  },
+  Error, InnerClient, Result, Stats,
+};
+use std::{
+  collections::{HashMap, HashSet},
+  sync::{
+    atomic::{AtomicBool, AtomicUsize, Ordering},
+    Arc, Mutex as SyncMutex, RwLock,
+  },
+  time::Duration,
+};
+use tokio::{
+  sync::Mutex,
+  task::JoinHandle,
+  time::{self, Instant},
+};
+
+// emits a diagnostic as a `tracing` event of the given level with the `tracing` feature, and nothing otherwise.
+macro_rules! diagnostic {
+  ($level:ident, $($arg:tt)+) => {{
+    #[cfg(feature = "tracing")]
+    tracing::$level!(target: "topgg::autoposter", $($arg)+);
+
+    #[cfg(not(feature = "tracing"))]
+    let _ = format_args!($($arg)+);
+  }};
+}
+
+/// A guild-related event coming from a third-party Discord Bot library, as understood by the built-in [`Handler`][crate::autoposter::Handler]s.
+///
+/// Events that aren't related to the bot's guilds simply return [`None`] everywhere.
+pub(crate) trait GuildEvent {
+  /// The IDs of every guild the bot is in, if this is a ready event.
+  #[inline(always)]
+  fn ready_guilds(&self) -> Option<Vec<u64>> {
+    None
+  }
+
+  /// The ID of the guild the bot was just added to.
+  #[inline(always)]
+  fn added(&self) -> Option<u64> {
+    None
+  }
+
+  /// The ID of a guild the bot is in whose details were just updated.
+  #[inline(always)]
+  fn updated(&self) -> Option<u64> {
+    None
+  }
+
+  /// The (approximate) amount of members of the guild the bot was just added to or that was just updated, if the event carries it.
+  #[inline(always)]
+  fn member_count(&self) -> Option<u64> {
+    None
+  }
+
+  /// The ID of the guild the bot was just removed from.
+  #[inline(always)]
+  fn removed(&self) -> Option<u64> {
+    None
+  }
+}
+
+/// When the built-in [`Handler`][crate::autoposter::Handler]s post the bot's statistics. (See [`Twilight::with_post_mode`][crate::autoposter::Twilight::with_post_mode] and [`Serenity::with_post_mode`][crate::autoposter::Serenity::with_post_mode])
+///
+/// Either way, posts are at least the [`Autoposter`][crate::Autoposter]'s interval apart, and guild events always keep the server count up to date.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum PostMode {
+  /// Post on guild events only, coalescing the events within an interval into a single post. Nothing is posted while no guild events happen.
+  EventsOnly,
+
+  /// Post once per interval only, starting with the first guild event. Guild events in between are picked up by the next post.
+  TimerOnly,
+
+  /// Post on guild events, and also once per interval while no guild events happen. This is the default.
+  #[default]
+  Hybrid,
+}
+
+/// How much the posted server count has to change between two successful posts for [`Handler::on_significant_change`][crate::autoposter::Handler::on_significant_change] to be called. (See [`Twilight::with_change_threshold`][crate::autoposter::Twilight::with_change_threshold] and [`Serenity::with_change_threshold`][crate::autoposter::Serenity::with_change_threshold])
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ChangeThreshold {
+  /// More than this amount of servers gained or lost.
+  Absolute(usize),
+
+  /// More than this percentage of the previous server count gained or lost, e.g. `50.0` for half of it. Any change from zero servers exceeds it.
+  Percent(f64),
+}
+
+impl ChangeThreshold {
+  /// Whether the change from `old` to `new` servers exceeds this threshold.
+  #[must_use]
+  pub fn is_exceeded(self, old: usize, new: usize) -> bool {
+    let change = old.abs_diff(new);
+
+    match self {
+      Self::Absolute(max) => change > max,
+      Self::Percent(_) if old == 0 => change != 0,
+      Self::Percent(max) => change as f64 / old as f64 * 100.0 > max,
+    }
+  }
+}
+
+/// The library-agnostic core of the built-in [`Handler`][crate::autoposter::Handler]s, which keeps track of the bot's guilds and posts its statistics at most once per minimum interval.
+///
+/// Changes made within the minimum interval are not lost: a single timer task is spawned to post the latest statistics once the interval allows it, and every other attempt in the meantime returns right away, unless that task is overdue, e.g. after a long gateway outage. Intervals are measured with a monotonic clock. Each post attempt also schedules the next one a minimum interval later regardless of guild events (except in [`PostMode::EventsOnly`]), so that bots whose guild count doesn't change still keep [Top.gg](https://top.gg) up to date.
+pub struct Poster {
+  // the member count of each guild, if known.
+  cache: Mutex<HashMap<u64, Option<u64>>>,
+  pub(super) stats: SharedStats,
+  client: Arc<InnerClient>,
+  min_interval: Duration,
+  created_at: Instant,
+  last_post: Mutex<Option<Instant>>,
+  last_success: Mutex<Option<Instant>>,
+  // failed posts don't count as recent posts, and the last one is retried on the next guild event.
+  retry_on_events: AtomicBool,
+  failed: AtomicBool,
+  // nothing is posted anymore, either because the token isn't allowed to or after shutting down.
+  stopped: AtomicBool,
+  // guild events are ignored while shutting down.
+  frozen: AtomicBool,
+  // everything but the HTTP request itself is done, e.g. for staging environments.
+  dry_run: AtomicBool,
+  // nothing is posted until a ready event set an authoritative server count.
+  suppress_until_ready: AtomicBool,
+  ready: AtomicBool,
+  // nothing is posted until every guild listed by the ready event arrived, except for the guilds left here.
+  wait_for_guilds: AtomicBool,
+  syncing: SyncMutex<HashSet<u64>>,
+  shutdown_timeout: RwLock<Option<Duration>>,
+  scheduled: AtomicBool,
+  // the pending timer task, along with when it's due.
+  timer: SyncMutex<Option<(Instant, JoinHandle<()>)>>,
+  coordinator: RwLock<Arc<dyn PostCoordinator>>,
+  mode: RwLock<PostMode>,
+  // the server count of the last successful post, for detecting significant changes.
+  change_threshold: RwLock<Option<ChangeThreshold>>,
+  last_posted_count: SyncMutex<Option<usize>>,
+  added_since_post: AtomicUsize,
+  removed_since_post: AtomicUsize,
+}
+
+/// Aborts the pending timer task, so that recreating autoposters doesn't leak tasks. This happens once the [`Autoposter`][crate::Autoposter] and every clone of its handler are dropped.
+impl Drop for Poster {
+  fn drop(&mut self) {
+    if let Some((_, timer)) = self.timer.get_mut().unwrap().take() {
+      timer.abort();
+
+      if !*self.stopped.get_mut() {
+        diagnostic!(
+          warn,
+          "Dropped the autoposter without shutting it down: its scheduled post is cancelled."
+        );
+      }
+    }
+  }
+}
+
+/// A private trait implemented by the built-in [`Handler`][crate::autoposter::Handler]s, which lets the [`Autoposter`][crate::Autoposter] access their [`Poster`].
+pub trait BuiltinHandler {
+  fn poster(&self) -> &Poster;
+}
+
+impl Poster {
+  #[inline(always)]
+  pub(super) fn new(client: Arc<InnerClient>, min_interval: Duration) -> Arc<Self> {
+    Arc::new(Self {
+      cache: Mutex::const_new(HashMap::new()),
+      stats: SharedStats::new(),
+      client,
+      min_interval,
+      created_at: Instant::now(),
+      last_post: Mutex::const_new(None),
+      last_success: Mutex::const_new(None),
+      retry_on_events: AtomicBool::new(false),
+      failed: AtomicBool::new(false),
+      stopped: AtomicBool::new(false),
+      frozen: AtomicBool::new(false),
+      dry_run: AtomicBool::new(false),
+      suppress_until_ready: AtomicBool::new(false),
+      ready: AtomicBool::new(false),
+      wait_for_guilds: AtomicBool::new(false),
+      syncing: SyncMutex::new(HashSet::new()),
+      shutdown_timeout: RwLock::new(None),
+      scheduled: AtomicBool::new(false),
+      timer: SyncMutex::new(None),
+      coordinator: RwLock::new(Arc::new(NoCoordinator)),
+      mode: RwLock::new(PostMode::default()),
+      change_threshold: RwLock::new(None),
+      last_posted_count: SyncMutex::new(None),
+      added_since_post: AtomicUsize::new(0),
+      removed_since_post: AtomicUsize::new(0),
+    })
+  }
+
+  /// Attempts to post stats after an event, as allowed by the [`PostMode`]. In [`TimerOnly`][PostMode::TimerOnly] mode, this only makes sure that the next post is scheduled.
+  pub(super) async fn try_post(self: &Arc<Self>) {
+    let mode = *self.mode.read().unwrap();
+
+    if mode != PostMode::TimerOnly {
+      return self.post_if_due().await;
+    }
+
+    if !self.stopped.load(Ordering::Relaxed) && !self.scheduled.load(Ordering::Acquire) {
+      let last = *self.last_post.lock().await;
+      self.schedule(self.remaining(last));
+    }
+  }
+
+  /// Attempts to post stats if the minimum interval has passed since the last post, otherwise schedules a post for when it does. Nothing is posted until a server count is known (or until the first ready event if [`set_suppress_until_ready`][Poster::set_suppress_until_ready] is enabled), or ever again once the token turned out not to be allowed to post them.
+  async fn post_if_due(self: &Arc<Self>) {
+    if self.stopped.load(Ordering::Relaxed)
+      || (self.scheduled.load(Ordering::Acquire) && !self.cancel_overdue_timer())
+    {
+      return;
+    }
+
+    if self.suppress_until_ready.load(Ordering::Relaxed) && !self.ready.load(Ordering::Acquire) {
+      diagnostic!(debug, "Skipped posting bot stats: not ready yet.");
+      return;
+    }
+
+    if !self.syncing.lock().unwrap().is_empty() {
+      diagnostic!(
+        debug,
+        "Skipped posting bot stats: guilds are still syncing."
+      );
+
+      // guilds still unavailable by then are counted as listed by the ready event.
+      self.schedule(self.min_interval);
+      return;
+    }
+
+    let stats = self.stats.stats.read().await;
+
+    if stats.server_count().is_none() {
+      diagnostic!(debug, "Skipped posting bot stats: no stats yet.");
+      return;
+    }
+
+    let mut last = self.last_post.lock().await;
+    let remaining = self.remaining(*last);
+
+    if !remaining.is_zero() {
+      drop(last);
+      self.schedule(remaining);
+
+      return;
+    }
+
+    let previous = last.replace(Instant::now());
+    drop(last);
+
+    let coordinator = Arc::clone(&*self.coordinator.read().unwrap());
+
+    // another process posts for this interval.
+    if !coordinator.try_acquire().await {
+      self.schedule(self.min_interval);
+      return;
+    }
+
+    // guilds added or removed while posting count towards the next post.
+    let added = self.added_since_post.load(Ordering::Relaxed);
+    let removed = self.removed_since_post.load(Ordering::Relaxed);
+
+    self.failed.store(false, Ordering::Relaxed);
+
+    let result = self.post(&stats).await;
+    let posted_count = stats.server_count();
+
+    // the hook may update the stats itself.
+    drop(stats);
+    self.stats.notify_post(&result).await;
+
+    match result {
+      Ok(_) => {
+        *self.last_success.lock().await = Some(Instant::now());
+        self.check_change(posted_count).await;
+
+        self.added_since_post.fetch_sub(added, Ordering::Relaxed);
+        self
+          .removed_since_post
+          .fetch_sub(removed, Ordering::Relaxed);
+
+        // post again even if no guild events happen in the meantime.
+        if *self.mode.read().unwrap() != PostMode::EventsOnly {
+          self.schedule(self.min_interval);
+        }
+      }
+      Err(Error::Ratelimit { retry_after }) => {
+        // retry as soon as allowed rather than after the whole interval.
+        *self.last_post.lock().await = None;
+        self.schedule(Duration::from_secs(retry_after.into()));
+
+        diagnostic!(
+          warn,
+          "Ratelimited while posting bot stats, retrying in {retry_after} seconds."
+        );
+      }
+      Err(Error::Forbidden) => {
+        self.stopped.store(true, Ordering::Relaxed);
+
+        diagnostic!(
+          warn,
+          "Stopped posting bot stats: this token isn't allowed to post them."
+        );
+      }
+      Err(e) => {
+        if self.retry_on_events.load(Ordering::Relaxed) {
+          *self.last_post.lock().await = previous;
+          self.failed.store(true, Ordering::Relaxed);
+        }
+
+        self.schedule(self.min_interval);
+
+        diagnostic!(warn, "Failed to post bot stats: {}", e);
+      }
+    }
+  }
+
+  /// Records that the bot was added to a guild, for [`guilds_added_since_last_post`][Poster::guilds_added_since_last_post].
+  #[inline(always)]
+  pub(super) fn record_added(&self) {
+    self.added_since_post.fetch_add(1, Ordering::Relaxed);
+  }
+
+  /// Records that the bot was removed from a guild, for [`guilds_removed_since_last_post`][Poster::guilds_removed_since_last_post].
+  #[inline(always)]
+  pub(super) fn record_removed(&self) {
+    self.removed_since_post.fetch_add(1, Ordering::Relaxed);
+  }
+
+  /// The amount of guilds the bot was added to since the last successful post.
+  #[inline(always)]
+  pub(super) fn guilds_added_since_last_post(&self) -> usize {
+    self.added_since_post.load(Ordering::Relaxed)
+  }
+
+  /// The amount of guilds the bot was removed from since the last successful post.
+  #[inline(always)]
+  pub(super) fn guilds_removed_since_last_post(&self) -> usize {
+    self.removed_since_post.load(Ordering::Relaxed)
+  }
+
+  /// Whether guild events are ignored as the bot is shutting down.
+  #[inline(always)]
+  pub(super) fn is_frozen(&self) -> bool {
+    self.frozen.load(Ordering::Acquire)
+  }
+
+  /// Ignores every guild event from now on, e.g. the guild deletes sent while the gateway closes.
+  #[inline(always)]
+  pub(super) fn begin_shutdown(&self) {
+    self.frozen.store(true, Ordering::Release);
+  }
+
+  /// Ignores every guild event from now on and posts the current stats one last time regardless of the interval, if any. Nothing is posted afterwards.
+  pub(super) async fn shutdown(&self) -> Result<()> {
+    self.begin_shutdown();
+
+    if let Some((_, timer)) = self.timer.lock().unwrap().take() {
+      timer.abort();
+    }
+
+    if self.stopped.swap(true, Ordering::AcqRel) {
+      return Ok(());
+    }
+
+    let stats = self.stats.stats.read().await;
+
+    if stats.server_count().is_none() {
+      return Ok(());
+    }
+
+    let timeout = *self.shutdown_timeout.read().unwrap();
+
+    match timeout {
+      Some(timeout) => time::timeout(timeout, self.post(&stats))
+        .await
+        .map_err(|_| Error::Timeout)??,
+      None => self.post(&stats).await?,
+    };
+
+    *self.last_success.lock().await = Some(Instant::now());
+
+    Ok(())
+  }
+
+  // the time left before the minimum interval since the `last` post has passed.
+  fn remaining(&self, last: Option<Instant>) -> Duration {
+    last.map_or(Duration::ZERO, |last| {
+      self.min_interval.saturating_sub(last.elapsed())
+    })
+  }
+
+  #[inline(always)]
+  pub(super) fn set_retry_on_events(&self, retry: bool) {
+    self.retry_on_events.store(retry, Ordering::Relaxed);
+  }
+
+  #[inline(always)]
+  pub(super) fn set_change_threshold(&self, threshold: ChangeThreshold) {
+    *self.change_threshold.write().unwrap() = Some(threshold);
+  }
+
+  // notifies the handler if the successfully posted server count changed significantly since the previous one.
+  async fn check_change(&self, posted_count: Option<usize>) {
+    let Some(new) = posted_count else {
+      return;
+    };
+
+    let old = self.last_posted_count.lock().unwrap().replace(new);
+    let threshold = *self.change_threshold.read().unwrap();
+
+    if let (Some(old), Some(threshold)) = (old, threshold) {
+      if threshold.is_exceeded(old, new) {
+        self.stats.notify_significant_change(old, new).await;
+      }
+    }
+  }
+
+  #[inline(always)]
+  pub(super) fn set_post_mode(&self, mode: PostMode) {
+    *self.mode.write().unwrap() = mode;
+  }
+
+  #[inline(always)]
+  pub(super) fn set_shutdown_timeout(&self, timeout: Duration) {
+    *self.shutdown_timeout.write().unwrap() = Some(timeout);
+  }
+
+  // posts the stats, or only logs them in dry-run mode.
+  async fn post(&self, stats: &Stats) -> PostResult {
+    if self.dry_run.load(Ordering::Relaxed) {
+      diagnostic!(debug, "[dry-run] Would have posted bot stats: {stats:?}");
+
+      return Ok(PostOutcome { dry_run: true });
+    }
+
+    self.client.post_stats(stats).await?;
+
+    Ok(PostOutcome { dry_run: false })
+  }
+
+  /// Posts the statistics of a single shard from now on.
+  ///
+  /// # Panics
+  ///
+  /// Panics if the shard ID is not lower than the shard count.
+  pub(super) fn set_shard(&self, shard_id: usize, shard_count: usize) {
+    // handlers are configured before handling any event, so nothing else holds the lock.
+    let guard = self.stats.stats.try_write().unwrap();
+
+    SharedStatsGuard { guard }.set_shard(shard_id, shard_count);
+  }
+
+  #[inline(always)]
+  pub(super) fn set_dry_run(&self, dry_run: bool) {
+    self.dry_run.store(dry_run, Ordering::Relaxed);
+  }
+
+  #[inline(always)]
+  pub(super) fn set_suppress_until_ready(&self, suppress: bool) {
+    self.suppress_until_ready.store(suppress, Ordering::Relaxed);
+  }
+
+  /// Whether to hold back posts after a ready event until every guild it listed arrived, for at most a minimum interval.
+  #[inline(always)]
+  pub(super) fn set_wait_for_guilds(&self, wait: bool) {
+    self.wait_for_guilds.store(wait, Ordering::Relaxed);
+  }
+
+  // records that a guild of the ready event arrived (or left), posting right away once it was the last one.
+  fn finish_guild_sync(&self, guild_id: u64) {
+    let mut syncing = self.syncing.lock().unwrap();
+
+    if syncing.remove(&guild_id) && syncing.is_empty() {
+      if let Some((_, timer)) = self.timer.lock().unwrap().take() {
+        timer.abort();
+        self.scheduled.store(false, Ordering::Release);
+      }
+    }
+  }
+
+  /// Records that the server count is now authoritative, e.g. after a ready event.
+  #[inline(always)]
+  pub(super) fn mark_ready(&self) {
+    self.ready.store(true, Ordering::Release);
+  }
+
+  #[inline(always)]
+  pub(super) fn set_coordinator(&self, coordinator: Arc<dyn PostCoordinator>) {
+    *self.coordinator.write().unwrap() = coordinator;
+  }
+
+  // a scheduled post will pick up the latest stats anyway, unless it's overdue, e.g. as the runtime stalled throughout a gateway outage, or retries a failed post that guild events may retry sooner. returns whether it was cancelled to post right away instead.
+  fn cancel_overdue_timer(&self) -> bool {
+    let mut timer = self.timer.lock().unwrap();
+
+    match timer.take() {
+      Some((due, task)) if due <= Instant::now() || self.failed.load(Ordering::Relaxed) => {
+        task.abort();
+        self.scheduled.store(false, Ordering::Release);
+
+        true
+      }
+      pending => {
+        *timer = pending;
+
+        false
+      }
+    }
+  }
+
+  // spawns the timer task posting the stats after `delay`, unless one is already pending. The task doesn't keep the poster alive.
+  fn schedule(self: &Arc<Self>, delay: Duration) {
+    if self.scheduled.swap(true, Ordering::AcqRel) {
+      return;
+    }
+
+    let poster = Arc::downgrade(self);
+
+    let timer = tokio::spawn(async move {
+      time::sleep(delay).await;
+
+      if let Some(poster) = poster.upgrade() {
+        poster.scheduled.store(false, Ordering::Release);
+        poster.syncing.lock().unwrap().clear();
+        poster.post_if_due().await;
+      }
+    });
+
+    *self.timer.lock().unwrap() = Some((Instant::now() + delay, timer));
+  }
+
+  /// The sum of the member counts of every guild that reported one, or [`None`] if none did.
+  pub(super) async fn approximate_member_count(&self) -> Option<u64> {
+    self
+      .cache
+      .lock()
+      .await
+      .values()
+      .flatten()
+      .fold(None, |total, count| Some(total.unwrap_or(0) + count))
+  }
+
+  /// Whether a post succeeded within the minimum interval plus `max_age`, measured from creation if none did yet.
+  pub(super) async fn is_healthy(&self, max_age: Duration) -> bool {
+    let since = self.last_success.lock().await.unwrap_or(self.created_at);
+
+    since.elapsed() <= self.min_interval + max_age
+  }
+
+  /// Updates the guild cache and server count from a [`GuildEvent`], then attempts to post stats. Unrelated events are ignored.
+  #[cfg(any(test, feature = "serenity"))]
+  pub(super) async fn handle_guild_event<E>(self: &Arc<Self>, event: &E)
+  where
+    E: GuildEvent + ?Sized,
+  {
+    if self.update_guilds(event).await {
+      self.try_post().await;
+    }
+  }
+
+  /// Updates the guild cache and server count from a [`GuildEvent`] without posting anything. Returns `false` if the event is unrelated or ignored while shutting down.
+  pub(super) async fn update_guilds<E>(&self, event: &E) -> bool
+  where
+    E: GuildEvent + ?Sized,
+  {
+    if self.is_frozen() {
+      return false;
+    }
+
+    {
+      let mut cache = self.cache.lock().await;
+
+      let changed = if let Some(guilds) = event.ready_guilds() {
+        if self.wait_for_guilds.load(Ordering::Relaxed) {
+          *self.syncing.lock().unwrap() = guilds.iter().copied().collect();
+        }
+
+        *cache = guilds
+          .into_iter()
+          .map(|guild_id| (guild_id, None))
+          .collect();
+        true
+      } else if let Some(guild_id) = event.added() {
+        let added = cache.insert(guild_id, event.member_count()).is_none();
+        self.finish_guild_sync(guild_id);
+
+        if added {
+          self.record_added();
+        }
+
+        added
+      } else if let Some(guild_id) = event.removed() {
+        let removed = cache.remove(&guild_id).is_some();
+        self.finish_guild_sync(guild_id);
+
+        if removed {
+          self.record_removed();
+        } else {
+          // deletes are only sent for guilds the bot was in, so the cache missed its create event.
+          diagnostic!(warn, "Ignored the deletion of untracked guild {guild_id}: the guild cache may be out of sync.");
+        }
+
+        removed
+      } else if let Some(guild_id) = event.updated() {
+        // updates never change the server count, and a missing member count doesn't erase the known one.
+        if let (Some(known), Some(member_count)) = (cache.get_mut(&guild_id), event.member_count())
+        {
+          *known = Some(member_count);
+        }
+
+        false
+      } else {
+        return false;
+      };
+
+      if changed {
+        let mut stats = self.stats.write().await;
+        stats.set_server_count(cache.len());
+      }
+    }
+
+    if event.ready_guilds().is_some() {
+      self.mark_ready();
+    }
+
+    true
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{
+    autoposter::AsClientSealed,
+    mock::{MockServer, Response},
+  };
+  use std::sync::atomic::AtomicUsize;
+  use tokio::time;
+
+  enum FakeEvent {
+    Ready(Vec<u64>),
+    Added(u64),
+    AddedWithMembers(u64, u64),
+    Updated(u64, u64),
+    Removed(u64),
+    Unrelated,
+  }
+
+  impl GuildEvent for FakeEvent {
+    fn ready_guilds(&self) -> Option<Vec<u64>> {
+      match self {
+        Self::Ready(guilds) => Some(guilds.clone()),
+        _ => None,
+      }
+    }
+
+    fn added(&self) -> Option<u64> {
+      match self {
+        Self::Added(guild_id) | Self::AddedWithMembers(guild_id, _) => Some(*guild_id),
+        _ => None,
+      }
+    }
+
+    fn updated(&self) -> Option<u64> {
+      match self {
+        Self::Updated(guild_id, _) => Some(*guild_id),
+        _ => None,
+      }
+    }
+
+    fn member_count(&self) -> Option<u64> {
+      match self {
+        Self::AddedWithMembers(_, member_count) | Self::Updated(_, member_count) => {
+          Some(*member_count)
+        }
+        _ => None,
+      }
+    }
+
+    fn removed(&self) -> Option<u64> {
+      match self {
+        Self::Removed(guild_id) => Some(*guild_id),
+        _ => None,
+      }
+    }
+  }
+
+  async fn server_count(poster: &Poster) -> Option<usize> {
+    poster.stats.stats.read().await.server_count()
+  }
+
+  #[tokio::test]
+  async fn nothing_is_posted_before_any_stats() {
+    let server = MockServer::start(|_| Response::new(200));
+    let poster = Poster::new(server.client().as_client(), Duration::from_secs(900));
+
+    poster.try_post().await;
+    assert_eq!(server_count(&poster).await, None);
+    assert!(server.requests().is_empty());
+
+    // the skipped attempt doesn't hold back the first real post.
+    poster.handle_guild_event(&FakeEvent::Added(1)).await;
+    assert_eq!(server.requests().len(), 1);
+  }
+
+  #[tokio::test]
+  async fn nothing_is_posted_before_ready_if_suppressed() {
+    let server = MockServer::start(|_| Response::new(200));
+    let poster = Poster::new(server.client().as_client(), Duration::from_secs(900));
+    poster.set_suppress_until_ready(true);
+
+    // guilds streaming in before ready only make up a partial count.
+    poster.handle_guild_event(&FakeEvent::Added(1)).await;
+    poster.handle_guild_event(&FakeEvent::Added(2)).await;
+    assert_eq!(server_count(&poster).await, Some(2));
+    assert!(server.requests().is_empty());
+
+    poster
+      .handle_guild_event(&FakeEvent::Ready(vec![1, 2, 3]))
+      .await;
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].body, br#"{"server_count":3}"#);
+  }
+
+  // a custom handler recording the posts of an embedded poster.
+  struct Recorder {
+    poster: Arc<Poster>,
+    posts: SyncMutex<Vec<Option<PostOutcome>>>,
+    changes: SyncMutex<Vec<(usize, usize)>>,
+  }
+
+  impl crate::autoposter::Handler for Recorder {
+    fn stats(&self) -> &SharedStats {
+      &self.poster.stats
+    }
+
+    async fn on_post(&self, result: &crate::autoposter::PostResult) {
+      self
+        .posts
+        .lock()
+        .unwrap()
+        .push(result.as_ref().ok().copied());
+    }
+
+    async fn on_significant_change(&self, old: usize, new: usize) {
+      self.changes.lock().unwrap().push((old, new));
+    }
+  }
+
+  const POSTED: PostOutcome = PostOutcome { dry_run: false };
+
+  #[tokio::test]
+  async fn handlers_are_notified_of_posts() {
+    let server = MockServer::start(|request| match request.body.as_slice() {
+      br#"{"server_count":1}"# => Response::new(500),
+      _ => Response::new(200),
+    });
+    let poster = Poster::new(server.client().as_client(), Duration::from_secs(900));
+    let autoposter = crate::Autoposter::new(
+      Recorder {
+        poster: Arc::clone(&poster),
+        posts: SyncMutex::new(Vec::new()),
+        changes: SyncMutex::new(Vec::new()),
+      },
+      Duration::from_secs(900),
+    );
+
+    poster
+      .handle_guild_event(&FakeEvent::Ready(vec![1, 2]))
+      .await;
+    assert_eq!(*autoposter.posts.lock().unwrap(), [Some(POSTED)]);
+
+    // skips the minimum interval.
+    let post_again = || async {
+      *poster.last_post.lock().await = None;
+      poster.scheduled.store(false, Ordering::Release);
+    };
+
+    post_again().await;
+    poster.handle_guild_event(&FakeEvent::Removed(2)).await;
+    assert_eq!(*autoposter.posts.lock().unwrap(), [Some(POSTED), None]);
+
+    // posts keep working once the handler is gone.
+    drop(autoposter);
+    post_again().await;
+    poster.handle_guild_event(&FakeEvent::Removed(1)).await;
+    assert_eq!(server.requests().len(), 3);
+  }
+
+  #[tokio::test(start_paused = true)]
+  async fn dropping_aborts_the_timer_task() {
+    let server = MockServer::start(|_| Response::new(200));
+    let poster = Poster::new(server.client().as_client(), Duration::from_secs(900));
+
+    poster
+      .handle_guild_event(&FakeEvent::Ready(vec![1, 2]))
+      .await;
+
+    let timer = poster
+      .timer
+      .lock()
+      .unwrap()
+      .as_ref()
+      .map(|(_, task)| task.abort_handle())
+      .unwrap();
+
+    drop(poster);
+    tokio::task::yield_now().await;
+    assert!(timer.is_finished());
+
+    time::sleep(Duration::from_secs(1800)).await;
+    assert_eq!(server.requests().len(), 1);
+  }
+
+  #[tokio::test]
+  async fn posts_wait_for_the_guilds_of_ready_if_enabled() {
+    let server = MockServer::start(|_| Response::new(200));
+    let poster = Poster::new(server.client().as_client(), Duration::from_secs(900));
+    poster.set_wait_for_guilds(true);
+
+    // large bots only get unavailable stubs on ready.
+    poster
+      .handle_guild_event(&FakeEvent::Ready(vec![1, 2, 3]))
+      .await;
+    poster.handle_guild_event(&FakeEvent::Added(1)).await;
+    poster.handle_guild_event(&FakeEvent::Removed(2)).await;
+    assert!(server.requests().is_empty());
+
+    poster.handle_guild_event(&FakeEvent::Added(3)).await;
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].body, br#"{"server_count":2}"#);
+  }
+
+  #[tokio::test(start_paused = true)]
+  async fn unavailable_guilds_of_ready_hold_back_posts_for_an_interval_at_most() {
+    let server = MockServer::start(|_| Response::new(200));
+    let poster = Poster::new(server.client().as_client(), Duration::from_secs(900));
+    poster.set_wait_for_guilds(true);
+
+    poster
+      .handle_guild_event(&FakeEvent::Ready(vec![1, 2]))
+      .await;
+    poster.handle_guild_event(&FakeEvent::Added(1)).await;
+    assert!(server.requests().is_empty());
+
+    // the stub of the guild that never arrived still counts.
+    time::sleep(Duration::from_secs(900)).await;
+
+    while server.requests().is_empty() {
+      time::sleep(Duration::from_millis(1)).await;
+    }
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].body, br#"{"server_count":2}"#);
+  }
+
+  #[tokio::test(start_paused = true)]
+  async fn events_only_mode_has_no_heartbeat() {
+    let server = MockServer::start(|_| Response::new(200));
+    let poster = Poster::new(server.client().as_client(), Duration::from_secs(900));
+    poster.set_post_mode(PostMode::EventsOnly);
+
+    poster
+      .handle_guild_event(&FakeEvent::Ready(vec![1, 2]))
+      .await;
+    assert_eq!(server.requests().len(), 1);
+
+    time::sleep(Duration::from_secs(1800)).await;
+    assert_eq!(server.requests().len(), 1);
+
+    poster.handle_guild_event(&FakeEvent::Added(3)).await;
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 2);
+    assert_eq!(requests[1].body, br#"{"server_count":3}"#);
+  }
+
+  #[tokio::test(start_paused = true)]
+  async fn timer_only_mode_posts_once_per_interval() {
+    let server = MockServer::start(|_| Response::new(200));
+    let poster = Poster::new(server.client().as_client(), Duration::from_secs(900));
+    poster.set_post_mode(PostMode::TimerOnly);
+
+    let wait_for_posts = |posts| {
+      let server = &server;
+
+      async move {
+        while server.requests().len() < posts {
+          time::sleep(Duration::from_millis(1)).await;
+        }
+      }
+    };
+
+    // the first event starts the timer right away.
+    poster
+      .handle_guild_event(&FakeEvent::Ready(vec![1, 2]))
+      .await;
+    wait_for_posts(1).await;
+
+    // events in between are only picked up by the next tick.
+    time::sleep(Duration::from_secs(600)).await;
+    poster.handle_guild_event(&FakeEvent::Added(3)).await;
+    assert_eq!(server.requests().len(), 1);
+
+    time::sleep(Duration::from_secs(300)).await;
+    wait_for_posts(2).await;
+
+    poster.handle_guild_event(&FakeEvent::Added(4)).await;
+    time::sleep(Duration::from_secs(60)).await;
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 2);
+    assert_eq!(requests[0].body, br#"{"server_count":2}"#);
+    assert_eq!(requests[1].body, br#"{"server_count":3}"#);
+  }
+
+  #[tokio::test(start_paused = true)]
+  async fn hybrid_mode_posts_on_events_and_periodically() {
+    let server = MockServer::start(|_| Response::new(200));
+    let poster = Poster::new(server.client().as_client(), Duration::from_secs(900));
+    assert_eq!(*poster.mode.read().unwrap(), PostMode::Hybrid);
+
+    poster
+      .handle_guild_event(&FakeEvent::Ready(vec![1, 2]))
+      .await;
+    assert_eq!(server.requests().len(), 1);
+
+    // events within the interval are coalesced into the next periodic post.
+    poster.handle_guild_event(&FakeEvent::Added(3)).await;
+    poster.handle_guild_event(&FakeEvent::Added(4)).await;
+    assert_eq!(server.requests().len(), 1);
+
+    time::sleep(Duration::from_secs(900)).await;
+
+    while server.requests().len() < 2 {
+      time::sleep(Duration::from_millis(1)).await;
+    }
+    assert_eq!(server.requests()[1].body, br#"{"server_count":4}"#);
+  }
+
+  #[tokio::test]
+  async fn handlers_are_notified_of_significant_changes() {
+    let server = MockServer::start(|_| Response::new(200));
+    let poster = Poster::new(server.client().as_client(), Duration::from_secs(900));
+    poster.set_change_threshold(ChangeThreshold::Percent(50.0));
+
+    let autoposter = crate::Autoposter::new(
+      Recorder {
+        poster: Arc::clone(&poster),
+        posts: SyncMutex::new(Vec::new()),
+        changes: SyncMutex::new(Vec::new()),
+      },
+      Duration::from_secs(900),
+    );
+
+    // skips the minimum interval.
+    let post_again = || async {
+      *poster.last_post.lock().await = None;
+      poster.scheduled.store(false, Ordering::Release);
+    };
+
+    poster
+      .handle_guild_event(&FakeEvent::Ready(vec![1, 2, 3, 4]))
+      .await;
+
+    post_again().await;
+    poster.handle_guild_event(&FakeEvent::Removed(4)).await;
+
+    // a bug halving the count.
+    post_again().await;
+    poster.handle_guild_event(&FakeEvent::Ready(vec![1])).await;
+
+    assert_eq!(server.requests().len(), 3);
+    assert_eq!(*autoposter.changes.lock().unwrap(), [(3, 1)]);
+  }
+
+  #[test]
+  fn change_thresholds() {
+    assert!(ChangeThreshold::Absolute(10).is_exceeded(100, 111));
+    assert!(ChangeThreshold::Absolute(10).is_exceeded(100, 89));
+    assert!(!ChangeThreshold::Absolute(10).is_exceeded(100, 110));
+
+    assert!(ChangeThreshold::Percent(50.0).is_exceeded(100, 49));
+    assert!(!ChangeThreshold::Percent(50.0).is_exceeded(100, 150));
+    assert!(ChangeThreshold::Percent(50.0).is_exceeded(0, 1));
+    assert!(!ChangeThreshold::Percent(50.0).is_exceeded(0, 0));
+  }
+
+  #[tokio::test]
+  async fn deleting_untracked_guilds_changes_nothing() {
+    let server = MockServer::start(|_| Response::new(200));
+    let poster = Poster::new(server.client().as_client(), Duration::from_secs(900));
+
+    poster
+      .handle_guild_event(&FakeEvent::Ready(vec![1, 2]))
+      .await;
+    assert_eq!(server.requests().len(), 1);
+
+    poster.handle_guild_event(&FakeEvent::Removed(3)).await;
+    assert_eq!(server_count(&poster).await, Some(2));
+    assert_eq!(poster.guilds_removed_since_last_post(), 0);
+    assert_eq!(server.requests().len(), 1);
+  }
+
+  #[tokio::test(start_paused = true)]
+  async fn first_stats_are_posted_right_away() {
+    let server = MockServer::start(|_| Response::new(200));
+    let poster = Poster::new(server.client().as_client(), Duration::from_secs(900));
+
+    poster
+      .handle_guild_event(&FakeEvent::Ready(vec![1, 2]))
+      .await;
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].body, br#"{"server_count":2}"#);
+  }
+
+  #[tokio::test(start_paused = true)]
+  async fn stats_are_posted_periodically_without_guild_events() {
+    let server = MockServer::start(|_| Response::new(200));
+    let poster = Poster::new(server.client().as_client(), Duration::from_secs(900));
+
+    poster
+      .handle_guild_event(&FakeEvent::Ready(vec![1, 2]))
+      .await;
+    assert_eq!(server.requests().len(), 1);
+
+    for posts in 2..=4 {
+      time::sleep(Duration::from_secs(900)).await;
+
+      while server.requests().len() < posts {
+        time::sleep(Duration::from_millis(1)).await;
+      }
+    }
+
+    assert!(server
+      .requests()
+      .iter()
+      .all(|request| request.body == br#"{"server_count":2}"#));
+
+    // nothing is posted after shutting down.
+    poster.shutdown().await.unwrap();
+    time::sleep(Duration::from_secs(1800)).await;
+    assert_eq!(server.requests().len(), 5);
+  }
+
+  #[tokio::test(start_paused = true)]
+  async fn reconnects_after_long_outages_post_right_away() {
+    use futures_util::FutureExt;
+
+    let server = MockServer::start(|_| Response::new(200));
+    let poster = Poster::new(server.client().as_client(), Duration::from_secs(900));
+
+    poster
+      .handle_guild_event(&FakeEvent::Ready(vec![1, 2]))
+      .await;
+    assert_eq!(server.requests().len(), 1);
+
+    // jumps hours ahead without yielding, like a runtime stalled throughout an outage, leaving the periodic post overdue.
+    let outage = || {
+      assert!(time::advance(Duration::from_secs(3 * 3600))
+        .now_or_never()
+        .is_none());
+    };
+
+    // a resumed session carries no guilds, so the built-in handlers simply attempt to post.
+    outage();
+    poster.try_post().await;
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 2);
+    assert_eq!(requests[1].body, br#"{"server_count":2}"#);
+
+    outage();
+    poster
+      .handle_guild_event(&FakeEvent::Ready(vec![1, 2, 3]))
+      .await;
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 3);
+    assert_eq!(requests[2].body, br#"{"server_count":3}"#);
+
+    // the overdue post was replaced rather than sent as well.
+    time::sleep(Duration::from_secs(899)).await;
+    assert_eq!(server.requests().len(), 3);
+  }
+
+  #[tokio::test(start_paused = true)]
+  async fn health_follows_successful_posts() {
+    // only the first post succeeds, not the periodic ones after it.
+    let attempts = AtomicUsize::new(0);
+    let server = MockServer::start(move |_| {
+      if attempts.fetch_add(1, Ordering::Relaxed) == 0 {
+        Response::new(200)
+      } else {
+        Response::new(500)
+      }
+    });
+    let poster = Poster::new(server.client().as_client(), Duration::from_secs(900));
+    let max_age = Duration::from_secs(60);
+
+    // within the grace period of a freshly created poster.
+    time::advance(Duration::from_secs(950)).await;
+    assert!(poster.is_healthy(max_age).await);
+
+    time::advance(Duration::from_secs(20)).await;
+    assert!(!poster.is_healthy(max_age).await);
+
+    poster.handle_guild_event(&FakeEvent::Added(1)).await;
+    assert!(poster.is_healthy(max_age).await);
+
+    time::advance(Duration::from_secs(959)).await;
+    assert!(poster.is_healthy(max_age).await);
+
+    time::advance(Duration::from_secs(2)).await;
+    assert!(!poster.is_healthy(max_age).await);
+  }
+
+  #[tokio::test(start_paused = true)]
+  async fn failed_posts_are_unhealthy() {
+    let server = MockServer::start(|_| Response::new(500));
+    let poster = Poster::new(server.client().as_client(), Duration::from_secs(900));
+
+    time::advance(Duration::from_secs(1000)).await;
+    poster.handle_guild_event(&FakeEvent::Added(1)).await;
+
+    assert_eq!(server.requests().len(), 1);
+    assert!(!poster.is_healthy(Duration::from_secs(60)).await);
+  }
+
+  #[tokio::test(start_paused = true)]
+  async fn failed_posts_are_retried_on_the_next_event_if_enabled() {
+    let attempts = AtomicUsize::new(0);
+    let server = MockServer::start(move |_| {
+      if attempts.fetch_add(1, Ordering::Relaxed) == 0 {
+        Response::new(500)
+      } else {
+        Response::new(200)
+      }
+    });
+    let poster = Poster::new(server.client().as_client(), Duration::from_secs(900));
+
+    poster.set_retry_on_events(true);
+    poster.handle_guild_event(&FakeEvent::Added(1)).await;
+    assert!(poster.last_success.lock().await.is_none());
+
+    time::advance(Duration::from_secs(1)).await;
+    poster.handle_guild_event(&FakeEvent::Added(2)).await;
+
+    assert_eq!(server.requests().len(), 2);
+    assert!(poster.last_success.lock().await.is_some());
+
+    // successful posts count as recent ones again.
+    poster.handle_guild_event(&FakeEvent::Added(3)).await;
+    assert_eq!(server.requests().len(), 2);
+  }
+
+  #[tokio::test(start_paused = true)]
+  async fn ratelimits_are_retried_after_retry_after() {
+    let attempts = AtomicUsize::new(0);
+    let server = MockServer::start(move |_| {
+      if attempts.fetch_add(1, Ordering::Relaxed) == 0 {
+        Response::new(429).body(r#"{"retry-after":60}"#)
+      } else {
+        Response::new(200)
+      }
+    });
+    let poster = Poster::new(server.client().as_client(), Duration::from_secs(900));
+
+    let start = Instant::now();
+    poster.handle_guild_event(&FakeEvent::Added(1)).await;
+    assert_eq!(server.requests().len(), 1);
+
+    time::sleep(Duration::from_secs(59)).await;
+    assert_eq!(server.requests().len(), 1);
+
+    while poster.last_success.lock().await.is_none() {
+      time::sleep(Duration::from_millis(1)).await;
+    }
+
+    // well before the interval has passed.
+    assert!(start.elapsed() < Duration::from_secs(120));
+    assert_eq!(server.requests().len(), 2);
+  }
+
+  #[tokio::test]
+  async fn member_counts_are_accumulated() {
+    let server = MockServer::start(|_| Response::new(200));
+    let poster = Poster::new(server.client().as_client(), Duration::from_secs(900));
+
+    poster
+      .handle_guild_event(&FakeEvent::Ready(vec![1, 2, 3]))
+      .await;
+    assert_eq!(poster.approximate_member_count().await, None);
+
+    // guilds listed on ready become available later on.
+    poster
+      .handle_guild_event(&FakeEvent::AddedWithMembers(1, 100))
+      .await;
+    poster
+      .handle_guild_event(&FakeEvent::AddedWithMembers(2, 50))
+      .await;
+    poster
+      .handle_guild_event(&FakeEvent::AddedWithMembers(4, 25))
+      .await;
+    assert_eq!(poster.approximate_member_count().await, Some(175));
+    assert_eq!(server_count(&poster).await, Some(4));
+
+    poster.handle_guild_event(&FakeEvent::Removed(1)).await;
+    assert_eq!(poster.approximate_member_count().await, Some(75));
+
+    // updates refresh known guilds only.
+    poster.handle_guild_event(&FakeEvent::Updated(2, 60)).await;
+    poster
+      .handle_guild_event(&FakeEvent::Updated(5, 1000))
+      .await;
+    assert_eq!(poster.approximate_member_count().await, Some(85));
+    assert_eq!(server_count(&poster).await, Some(3));
+
+    // the member count is not part of the posted stats.
+    assert_eq!(server.requests()[0].body, br#"{"server_count":3}"#);
+  }
+
+  #[tokio::test(start_paused = true)]
+  async fn forbidden_stops_posting() {
+    let server = MockServer::start(|_| Response::new(403));
+    let poster = Poster::new(server.client().as_client(), Duration::from_secs(900));
+
+    poster.handle_guild_event(&FakeEvent::Added(1)).await;
+    time::advance(Duration::from_secs(1800)).await;
+    poster.handle_guild_event(&FakeEvent::Added(2)).await;
+
+    assert_eq!(server.requests().len(), 1);
+    assert_eq!(server_count(&poster).await, Some(2));
+  }
+
+  #[tokio::test(start_paused = true)]
+  async fn bursts_are_posted_once_per_interval() {
+    let server = MockServer::start(|_| Response::new(200));
+    let poster = Poster::new(server.client().as_client(), Duration::from_secs(900));
+
+    for guild_id in 0..1000 {
+      poster.handle_guild_event(&FakeEvent::Added(guild_id)).await;
+    }
+
+    assert_eq!(server.requests().len(), 1);
+
+    // the timer task posts the latest stats once the interval has passed.
+    time::sleep(Duration::from_secs(900)).await;
+
+    while server.requests().len() < 2 {
+      time::sleep(Duration::from_millis(1)).await;
+    }
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 2);
+    assert_eq!(requests[1].body, br#"{"server_count":1000}"#);
+  }
+
+  #[tokio::test]
+  async fn guild_deletes_are_ignored_while_shutting_down() {
+    let server = MockServer::start(|_| Response::new(200));
+    let poster = Poster::new(server.client().as_client(), Duration::from_secs(900));
+
+    poster
+      .handle_guild_event(&FakeEvent::Ready(vec![1, 2, 3]))
+      .await;
+
+    poster.begin_shutdown();
+    poster.handle_guild_event(&FakeEvent::Removed(1)).await;
+    poster.handle_guild_event(&FakeEvent::Removed(2)).await;
+    assert_eq!(server_count(&poster).await, Some(3));
+
+    // the final post goes out regardless of the interval.
+    poster.shutdown().await.unwrap();
+    poster.try_post().await;
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 2);
+    assert_eq!(requests[1].body, br#"{"server_count":3}"#);
+
+    assert!(poster.shutdown().await.is_ok());
+    assert_eq!(server.requests().len(), 2);
+  }
+
+  #[tokio::test]
+  async fn slow_final_posts_time_out() {
+    let server = MockServer::start(|_| {
+      std::thread::sleep(Duration::from_millis(500));
+      Response::new(200)
+    });
+    let poster = Poster::new(server.client().as_client(), Duration::from_secs(900));
+
+    poster.stats.write().await.set_server_count(1);
+    poster.set_shutdown_timeout(Duration::from_millis(50));
+
+    let start = Instant::now();
+
+    assert!(matches!(poster.shutdown().await, Err(Error::Timeout)));
+    assert!(start.elapsed() < Duration::from_millis(500));
+  }
+
+  #[tokio::test]
+  async fn dry_runs_send_nothing() {
+    let server = MockServer::start(|_| Response::new(200));
+    let poster = Poster::new(server.client().as_client(), Duration::from_secs(900));
+    let autoposter = crate::Autoposter::new(
+      Recorder {
+        poster: Arc::clone(&poster),
+        posts: SyncMutex::new(Vec::new()),
+        changes: SyncMutex::new(Vec::new()),
+      },
+      Duration::from_secs(900),
+    );
+
+    poster.set_dry_run(true);
+    poster
+      .handle_guild_event(&FakeEvent::Ready(vec![1, 2]))
+      .await;
+    poster.shutdown().await.unwrap();
+
+    // the attempts went through as if they had been posted, but handlers can tell.
+    assert!(server.requests().is_empty());
+    assert!(poster.last_success.lock().await.is_some());
+    assert_eq!(
+      *autoposter.posts.lock().unwrap(),
+      [Some(PostOutcome { dry_run: true })]
+    );
+  }
+
+  // a lock held for the whole test, standing in for a distributed one.
+  #[derive(Default)]
+  struct InMemoryCoordinator {
+    locked: AtomicBool,
+  }
+
+  #[async_trait::async_trait]
+  impl PostCoordinator for InMemoryCoordinator {
+    async fn try_acquire(&self) -> bool {
+      !self.locked.swap(true, Ordering::AcqRel)
+    }
+  }
+
+  #[tokio::test]
+  async fn only_the_process_acquiring_the_coordinator_posts() {
+    let server = MockServer::start(|_| Response::new(200));
+    let coordinator = Arc::new(InMemoryCoordinator::default());
+    let processes = [
+      Poster::new(server.client().as_client(), Duration::from_secs(900)),
+      Poster::new(server.client().as_client(), Duration::from_secs(900)),
+    ];
+
+    for poster in &processes {
+      poster.set_coordinator(Arc::clone(&coordinator) as _);
+      poster.handle_guild_event(&FakeEvent::Added(1)).await;
+    }
+
+    assert_eq!(server.requests().len(), 1);
+    assert!(processes[0].last_success.lock().await.is_some());
+    assert!(processes[1].last_success.lock().await.is_none());
+  }
+
+  #[tokio::test(start_paused = true)]
+  async fn churn_is_counted_since_the_last_post() {
+    let server = MockServer::start(|_| Response::new(200));
+    let poster = Poster::new(server.client().as_client(), Duration::from_secs(900));
+
+    poster
+      .handle_guild_event(&FakeEvent::Ready(vec![1, 2, 3]))
+      .await;
+    assert_eq!(poster.guilds_added_since_last_post(), 0);
+
+    poster.handle_guild_event(&FakeEvent::Added(4)).await;
+    poster.handle_guild_event(&FakeEvent::Added(5)).await;
+    poster.handle_guild_event(&FakeEvent::Added(5)).await;
+    poster.handle_guild_event(&FakeEvent::Removed(1)).await;
+    poster.handle_guild_event(&FakeEvent::Removed(6)).await;
+    assert_eq!(poster.guilds_added_since_last_post(), 2);
+    assert_eq!(poster.guilds_removed_since_last_post(), 1);
+
+    // the timer task posts the churn above.
+    while server.requests().len() < 2 {
+      time::sleep(Duration::from_secs(1)).await;
+    }
+
+    while poster.guilds_added_since_last_post() != 0 {
+      time::sleep(Duration::from_millis(1)).await;
+    }
+
+    assert_eq!(poster.guilds_removed_since_last_post(), 0);
+  }
+
+  #[tokio::test]
+  async fn guild_events_update_the_server_count() {
+    let server = MockServer::start(|_| Response::new(200));
+    let poster = Poster::new(server.client().as_client(), Duration::from_secs(900));
+
+    poster
+      .handle_guild_event(&FakeEvent::Ready(vec![1, 2, 3]))
+      .await;
+    assert_eq!(server_count(&poster).await, Some(3));
+
+    poster.handle_guild_event(&FakeEvent::Added(4)).await;
+    poster.handle_guild_event(&FakeEvent::Added(4)).await;
+    assert_eq!(server_count(&poster).await, Some(4));
+
+    poster.handle_guild_event(&FakeEvent::Removed(1)).await;
+    poster.handle_guild_event(&FakeEvent::Removed(5)).await;
+    poster.handle_guild_event(&FakeEvent::Unrelated).await;
+    assert_eq!(server_count(&poster).await, Some(3));
+
+    // only the first event was outside of the minimum interval.
+    let requests = server.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].path, "/bots/stats");
+    assert_eq!(requests[0].body, br#"{"server_count":3}"#);
+  }
+}
@@ -1,33 +1,27 @@
 use crate::{
-  autoposter::{Handler, SharedStats},
+  autoposter::{
+    AsClient, BuiltinHandler, ChangeThreshold, GuildEvent, Handler, PostMode, Poster, SharedStats,
+    MIN_AUTOPOST_INTERVAL,
+  },
   InnerClient,
 };
 use serenity::{
   client::{Context, EventHandler, FullEvent},
   model::{
+    event::ResumedEvent,
     gateway::Ready,
-    guild::{Guild, UnavailableGuild},
-    id::GuildId,
+    guild::{Guild, PartialGuild, UnavailableGuild},
   },
 };
-use std::{
-  sync::Arc,
-  time::{Duration, Instant},
-};
-use tokio::sync::Mutex;
+use std::{sync::Arc, time::Duration};
 
-#[cfg(not(feature = "serenity-cached"))]
-use std::collections::HashSet;
+#[cfg(feature = "serenity-cached")]
+use serenity::model::id::GuildId;
 
 /// A built-in [`Handler`] for the [serenity] library.
 #[must_use]
 pub struct Serenity {
-  #[cfg(not(feature = "serenity-cached"))]
-  cache: Mutex<HashSet<GuildId>>,
-  stats: SharedStats,
-  client: Arc<InnerClient>,
-  min_interval: Duration,
-  last_post: Mutex<Option<Instant>>,
+  poster: Arc<Poster>,
 }
 
 #[allow(unused_variables)]
@@ -35,34 +29,82 @@ impl Serenity {
   #[inline(always)]
   pub(super) fn new(client: Arc<InnerClient>, min_interval: Duration) -> Self {
     Self {
-      #[cfg(not(feature = "serenity-cached"))]
-      cache: Mutex::const_new(HashSet::new()),
-      stats: SharedStats::new(),
-      client,
-      min_interval,
-      last_post: Mutex::const_new(None),
+      poster: Poster::new(client, min_interval),
     }
   }
 
-  /// Attempts to post stats if the minimum interval has passed since the last post.
-  async fn try_post(&self) {
-    let now = Instant::now();
-    let mut last = self.last_post.lock().await;
-    if last.map_or(true, |l| now.duration_since(l) >= self.min_interval) {
-      *last = Some(now);
-      drop(last);
+  /// Creates a standalone [`Serenity`] handler, e.g. to embed it in your own [`Handler`] alongside other state. [`Autoposter::serenity`][crate::Autoposter::serenity] is a shortcut for wrapping it in an [`Autoposter`][crate::Autoposter] right away.
+  ///
+  /// - `client` can either be a reference to an existing [`Client`][crate::Client] or a [`&str`][std::str] representing a [Top.gg API](https://docs.top.gg) token.
+  /// - `interval` is the minimum interval between two posts, and should be the same as the one given to [`Autoposter::new`][crate::Autoposter::new].
+  ///
+  /// # Panics
+  ///
+  /// Panics if the interval argument is shorter than [`MIN_AUTOPOST_INTERVAL`] (15 minutes).
+  pub fn from_client<C>(client: &C, interval: Duration) -> Self
+  where
+    C: AsClient,
+  {
+    assert!(
+      interval >= MIN_AUTOPOST_INTERVAL,
+      "The interval mustn't be shorter than 15 minutes."
+    );
 
-      let stats = self.stats.stats.read().await;
-      if let Err(e) = self.client.post_stats(&stats).await {
-        eprintln!("Failed to post bot stats: {}", e);
-      }
-    }
+    Self::new(client.as_client(), interval)
+  }
+
+  /// Posts the statistics of a single shard, e.g. when each shard runs in its own process with its own [`Serenity`] handler.
+  ///
+  /// # Panics
+  ///
+  /// Panics if the shard ID is not lower than the shard count, rather than posting statistics [Top.gg](https://top.gg) rejects every interval.
+  #[inline(always)]
+  pub fn with_shard(self, shard_id: usize, shard_count: usize) -> Self {
+    self.poster.set_shard(shard_id, shard_count);
+    self
+  }
+
+  /// Whether to hold back every post until the first ready event, instead of posting the partial server counts that guilds streaming in beforehand add up to. This is stricter than [`ClientBuilder::reject_zero_server_count`][crate::ClientBuilder::reject_zero_server_count], which only catches a count of zero. Defaults to `false`.
+  #[inline(always)]
+  pub fn with_suppress_until_ready(self, suppress: bool) -> Self {
+    self.poster.set_suppress_until_ready(suppress);
+    self
+  }
+
+  /// Whether to hold back posts after a ready event until every guild it listed arrived, instead of posting right away. The ready event of bots in many guilds only lists unavailable guilds, which arrive one by one afterwards. Guilds still unavailable after the [`Autoposter`][crate::Autoposter]'s interval are counted as listed by the ready event. Defaults to `false`. With the `serenity-cached` feature, posts already wait for [serenity]'s cache to be ready instead.
+  #[inline(always)]
+  pub fn with_wait_for_guilds(self, wait: bool) -> Self {
+    self.poster.set_wait_for_guilds(wait);
+    self
+  }
+
+  /// Sets when this handler posts the bot's stats. Defaults to [`PostMode::Hybrid`].
+  #[inline(always)]
+  pub fn with_post_mode(self, mode: PostMode) -> Self {
+    self.poster.set_post_mode(mode);
+    self
+  }
+
+  /// Whether a post that failed (other than being ratelimited or forbidden) is retried on the next guild event rather than a minimum interval later, as it doesn't count as a recent post then. Every guild event retries it until a post succeeds, with the usual retry a minimum interval later as a fallback. Disabled by default.
+  #[inline(always)]
+  pub fn with_retry_on_events(self, retry: bool) -> Self {
+    self.poster.set_retry_on_events(retry);
+    self
+  }
+
+  /// Calls [`Handler::on_significant_change`] whenever the posted server count changes by more than `threshold` between two successful posts. Disabled by default.
+  #[inline(always)]
+  pub fn with_change_threshold(self, threshold: ChangeThreshold) -> Self {
+    self.poster.set_change_threshold(threshold);
+    self
   }
 
   /// Handles an entire [serenity] [`FullEvent`] enum. This can be used in [serenity] frameworks.
   pub async fn handle(&self, ctx: &Context, event: &FullEvent) {
     match event {
-      FullEvent::Ready { data_about_bot } => self.on_ready(&data_about_bot.guilds).await,
+      FullEvent::Ready { data_about_bot } => self.poster.handle_guild_event(data_about_bot).await,
+
+      FullEvent::Resume { .. } => self.on_resume().await,
 
       #[cfg(feature = "serenity-cached")]
       FullEvent::CacheReady { guilds } => self.on_cache_ready(guilds.len()).await,
@@ -75,17 +117,20 @@ impl Serenity {
           if #[cfg(feature = "serenity-cached")] {
             self.on_guild_create(ctx.cache.guilds().len(), is_new.unwrap_or(false)).await;
           } else {
-            self.on_guild_create(guild.id).await;
+            self.poster.handle_guild_event(guild).await;
           }
         }
       }
 
+      #[cfg(not(feature = "serenity-cached"))]
+      FullEvent::GuildUpdate { new_data, .. } => self.poster.handle_guild_event(new_data).await,
+
       FullEvent::GuildDelete { incomplete, .. } => {
         cfg_if::cfg_if! {
           if #[cfg(feature = "serenity-cached")] {
             self.on_guild_delete(ctx.cache.guilds().len()).await;
           } else {
-            self.on_guild_delete(incomplete.id).await;
+            self.poster.handle_guild_event(incomplete).await;
           }
         }
       }
@@ -94,76 +139,99 @@ impl Serenity {
     }
   }
 
-  async fn on_ready(&self, guilds: &[UnavailableGuild]) {
-    {
-      let mut stats = self.stats.write().await;
-      stats.set_server_count(guilds.len());
-    }
-
-    #[cfg(not(feature = "serenity-cached"))]
-    {
-      let mut cache = self.cache.lock().await;
-      *cache = guilds.iter().map(|g| g.id).collect();
+  // a reconnect may follow a long outage, so it posts right away if the interval allows it.
+  async fn on_resume(&self) {
+    if !self.poster.is_frozen() {
+      self.poster.try_post().await;
     }
-
-    self.try_post().await;
   }
 
   #[cfg(feature = "serenity-cached")]
   async fn on_cache_ready(&self, guild_count: usize) {
+    if self.poster.is_frozen() {
+      return;
+    }
+
     {
-      let mut stats = self.stats.write().await;
+      let mut stats = self.poster.stats.write().await;
       stats.set_server_count(guild_count);
     }
-    self.try_post().await;
+    self.poster.mark_ready();
+    self.poster.try_post().await;
   }
 
   #[cfg(feature = "serenity-cached")]
   async fn on_shards_ready(&self, shard_count: u32) {
-    let mut stats = self.stats.write().await;
+    let mut stats = self.poster.stats.write().await;
     stats.set_shard_count(shard_count as usize);
   }
 
   #[cfg(feature = "serenity-cached")]
   async fn on_guild_create(&self, guild_count: usize, is_new: bool) {
-    if is_new {
-      let mut stats = self.stats.write().await;
-      stats.set_server_count(guild_count);
+    if self.poster.is_frozen() {
+      return;
     }
-    self.try_post().await;
-  }
 
-  #[cfg(not(feature = "serenity-cached"))]
-  async fn on_guild_create(&self, guild_id: GuildId) {
-    {
-      let mut cache = self.cache.lock().await;
-      if cache.insert(guild_id) {
-        let mut stats = self.stats.write().await;
-        stats.set_server_count(cache.len());
-      }
+    if is_new {
+      self.poster.record_added();
+
+      let mut stats = self.poster.stats.write().await;
+      stats.set_server_count(guild_count);
     }
-    self.try_post().await;
+    self.poster.try_post().await;
   }
 
   #[cfg(feature = "serenity-cached")]
   async fn on_guild_delete(&self, guild_count: usize) {
+    if self.poster.is_frozen() {
+      return;
+    }
+
+    self.poster.record_removed();
+
     {
-      let mut stats = self.stats.write().await;
+      let mut stats = self.poster.stats.write().await;
       stats.set_server_count(guild_count);
     }
-    self.try_post().await;
+    self.poster.try_post().await;
   }
+}
 
-  #[cfg(not(feature = "serenity-cached"))]
-  async fn on_guild_delete(&self, guild_id: GuildId) {
-    {
-      let mut cache = self.cache.lock().await;
-      if cache.remove(&guild_id) {
-        let mut stats = self.stats.write().await;
-        stats.set_server_count(cache.len());
-      }
-    }
-    self.try_post().await;
+impl GuildEvent for Ready {
+  #[inline(always)]
+  fn ready_guilds(&self) -> Option<Vec<u64>> {
+    Some(self.guilds.iter().map(|guild| guild.id.get()).collect())
+  }
+}
+
+impl GuildEvent for Guild {
+  #[inline(always)]
+  fn added(&self) -> Option<u64> {
+    Some(self.id.get())
+  }
+
+  #[inline(always)]
+  fn member_count(&self) -> Option<u64> {
+    Some(self.member_count)
+  }
+}
+
+impl GuildEvent for PartialGuild {
+  #[inline(always)]
+  fn updated(&self) -> Option<u64> {
+    Some(self.id.get())
+  }
+
+  #[inline(always)]
+  fn member_count(&self) -> Option<u64> {
+    self.approximate_member_count
+  }
+}
+
+impl GuildEvent for UnavailableGuild {
+  #[inline(always)]
+  fn removed(&self) -> Option<u64> {
+    Some(self.id.get())
   }
 }
 
@@ -171,7 +239,11 @@ impl Serenity {
 #[allow(unused_variables)]
 impl EventHandler for Serenity {
   async fn ready(&self, ctx: Context, data_about_bot: Ready) {
-    self.on_ready(&data_about_bot.guilds).await;
+    self.poster.handle_guild_event(&data_about_bot).await;
+  }
+
+  async fn resume(&self, ctx: Context, event: ResumedEvent) {
+    self.on_resume().await;
   }
 
   #[cfg(feature = "serenity-cached")]
@@ -189,25 +261,128 @@ impl EventHandler for Serenity {
       if #[cfg(feature = "serenity-cached")] {
         self.on_guild_create(ctx.cache.guilds().len(), is_new.unwrap_or(false)).await;
       } else {
-        self.on_guild_create(guild.id).await;
+        self.poster.handle_guild_event(&guild).await;
       }
     }
   }
 
+  #[cfg(not(feature = "serenity-cached"))]
+  async fn guild_update(
+    &self,
+    ctx: Context,
+    old_data_if_available: Option<Guild>,
+    new_data: PartialGuild,
+  ) {
+    self.poster.handle_guild_event(&new_data).await;
+  }
+
   async fn guild_delete(&self, ctx: Context, incomplete: UnavailableGuild, full: Option<Guild>) {
     cfg_if::cfg_if! {
       if #[cfg(feature = "serenity-cached")] {
         self.on_guild_delete(ctx.cache.guilds().len()).await;
       } else {
-        self.on_guild_delete(incomplete.id).await;
+        self.poster.handle_guild_event(&incomplete).await;
       }
     }
   }
 }
 
+impl Serenity {
+  /// The amount of guilds the bot was added to since the last successful post, e.g. for charting churn.
+  #[inline(always)]
+  pub fn guilds_added_since_last_post(&self) -> usize {
+    self.poster.guilds_added_since_last_post()
+  }
+
+  /// The amount of guilds the bot was removed from since the last successful post, e.g. for charting churn.
+  #[inline(always)]
+  pub fn guilds_removed_since_last_post(&self) -> usize {
+    self.poster.guilds_removed_since_last_post()
+  }
+}
+
+impl BuiltinHandler for Serenity {
+  #[inline(always)]
+  fn poster(&self) -> &Poster {
+    &self.poster
+  }
+}
+
 impl Handler for Serenity {
+  const KIND: &'static str = "serenity";
+
   #[inline(always)]
   fn stats(&self) -> &SharedStats {
-    &self.stats
+    &self.poster.stats
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{
+    mock::{MockServer, Response},
+    Autoposter, Client,
+  };
+
+  // a custom handler composing the built-in one.
+  struct Composite {
+    serenity: Serenity,
+    custom_state: usize,
+  }
+
+  impl Handler for Composite {
+    fn stats(&self) -> &SharedStats {
+      self.serenity.stats()
+    }
+  }
+
+  #[tokio::test]
+  async fn reports_its_kind() {
+    let client = Client::new(String::from("token"));
+
+    assert_eq!(
+      Autoposter::serenity(&client, MIN_AUTOPOST_INTERVAL).handler_kind(),
+      "serenity"
+    );
+  }
+
+  #[tokio::test]
+  async fn can_be_embedded_in_custom_handlers() {
+    let client = Client::new(String::from("token"));
+    let autoposter = Autoposter::new(
+      Composite {
+        serenity: Serenity::from_client(&client, MIN_AUTOPOST_INTERVAL),
+        custom_state: 0,
+      },
+      MIN_AUTOPOST_INTERVAL,
+    );
+
+    autoposter.stats().write().await.set_server_count(42);
+
+    assert_eq!(autoposter.custom_state, 0);
+    assert_eq!(
+      autoposter
+        .serenity
+        .stats()
+        .stats
+        .read()
+        .await
+        .server_count(),
+      Some(42)
+    );
+  }
+
+  #[tokio::test]
+  async fn resumes_post_right_away() {
+    let server = MockServer::start(|_| Response::new(200));
+    let serenity = Serenity::from_client(&server.client(), MIN_AUTOPOST_INTERVAL);
+
+    serenity.stats().write().await.set_server_count(1);
+    serenity.on_resume().await;
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].body, br#"{"server_count":1}"#);
   }
 }
@@ -1,95 +1,483 @@
 use crate::{
-  autoposter::{Handler, SharedStats},
+  autoposter::{
+    AsClient, BuiltinHandler, ChangeThreshold, GuildEvent, Handler, PostMode, Poster, SharedStats,
+    MIN_AUTOPOST_INTERVAL,
+  },
   InnerClient,
 };
-use std::{
-  collections::HashSet,
-  sync::Arc,
-  time::{Duration, Instant},
-};
-use tokio::sync::Mutex;
+use std::{sync::Arc, time::Duration};
+#[cfg(feature = "twilight-cached")]
+use twilight_cache_inmemory::InMemoryCache;
 use twilight_model::gateway::event::Event;
 
+bitflags::bitflags! {
+  /// The [twilight](https://twilight.rs) events that make a [`Twilight`] handler attempt to post the bot's stats. (See [`Twilight::with_triggers`])
+  ///
+  /// Guild events always keep the server count up to date regardless, they just aren't posted until a triggering event occurs.
+  ///
+  /// # Examples
+  ///
+  /// Basic usage:
+  ///
+  /// ```rust,no_run
+  /// use topgg::{
+  ///   autoposter::{PostTriggers, Twilight, MIN_AUTOPOST_INTERVAL},
+  ///   Client,
+  /// };
+  ///
+  /// let client = Client::new(std::env::var("TOPGG_TOKEN").unwrap());
+  /// let twilight = Twilight::from_client(&client, MIN_AUTOPOST_INTERVAL)
+  ///   .with_triggers(PostTriggers::READY | PostTriggers::RESUMED);
+  /// ```
+  #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+  pub struct PostTriggers: u8 {
+    /// [`Event::Ready`].
+    const READY = 1 << 0;
+
+    /// [`Event::GuildCreate`].
+    const GUILD_CREATE = 1 << 1;
+
+    /// [`Event::GuildDelete`].
+    const GUILD_DELETE = 1 << 2;
+
+    /// [`Event::Resumed`].
+    const RESUMED = 1 << 3;
+
+    /// [`Event::GuildUpdate`].
+    const GUILD_UPDATE = 1 << 4;
+  }
+}
+
+impl PostTriggers {
+  /// The trigger corresponding to an [`Event`], which is empty for events that can't trigger a post.
+  fn of(event: &Event) -> Self {
+    match event {
+      Event::Ready(_) => Self::READY,
+      Event::GuildCreate(_) => Self::GUILD_CREATE,
+      Event::GuildDelete(_) => Self::GUILD_DELETE,
+      Event::Resumed => Self::RESUMED,
+      Event::GuildUpdate(_) => Self::GUILD_UPDATE,
+      _ => Self::empty(),
+    }
+  }
+}
+
+impl Default for PostTriggers {
+  /// [`READY`][PostTriggers::READY], [`GUILD_CREATE`][PostTriggers::GUILD_CREATE], [`GUILD_DELETE`][PostTriggers::GUILD_DELETE] and [`RESUMED`][PostTriggers::RESUMED], as a reconnect may follow a long outage.
+  #[inline(always)]
+  fn default() -> Self {
+    Self::READY | Self::GUILD_CREATE | Self::GUILD_DELETE | Self::RESUMED
+  }
+}
+
 /// A built-in [`Handler`] for the [twilight](https://twilight.rs) library.
 pub struct Twilight {
-  cache: Mutex<HashSet<u64>>,
-  stats: SharedStats,
-  client: Arc<InnerClient>,
-  min_interval: Duration,
-  last_post: Mutex<Option<Instant>>,
+  poster: Arc<Poster>,
+  triggers: PostTriggers,
 }
 
 impl Twilight {
   #[inline(always)]
   pub(super) fn new(client: Arc<InnerClient>, min_interval: Duration) -> Self {
     Self {
-      cache: Mutex::const_new(HashSet::new()),
-      stats: SharedStats::new(),
-      client,
-      min_interval,
-      last_post: Mutex::const_new(None),
+      poster: Poster::new(client, min_interval),
+      triggers: PostTriggers::default(),
     }
   }
 
-  /// Attempts to post stats if the minimum interval has passed since the last post.
-  async fn try_post(&self) {
-    let now = Instant::now();
-    let mut last = self.last_post.lock().await;
-    if last.map_or(true, |l| now.duration_since(l) >= self.min_interval) {
-      *last = Some(now);
-      drop(last);
+  /// Creates a standalone [`Twilight`] handler, e.g. to embed it in your own [`Handler`] alongside other state. [`Autoposter::twilight`][crate::Autoposter::twilight] is a shortcut for wrapping it in an [`Autoposter`][crate::Autoposter] right away.
+  ///
+  /// - `client` can either be a reference to an existing [`Client`][crate::Client] or a [`&str`][std::str] representing a [Top.gg API](https://docs.top.gg) token.
+  /// - `interval` is the minimum interval between two posts, and should be the same as the one given to [`Autoposter::new`][crate::Autoposter::new].
+  ///
+  /// # Panics
+  ///
+  /// Panics if the interval argument is shorter than [`MIN_AUTOPOST_INTERVAL`] (15 minutes).
+  pub fn from_client<C>(client: &C, interval: Duration) -> Self
+  where
+    C: AsClient,
+  {
+    assert!(
+      interval >= MIN_AUTOPOST_INTERVAL,
+      "The interval mustn't be shorter than 15 minutes."
+    );
 
-      let stats = self.stats.stats.read().await;
-      if let Err(e) = self.client.post_stats(&stats).await {
-        eprintln!("Failed to post bot stats: {}", e);
-      }
-    }
+    Self::new(client.as_client(), interval)
+  }
+
+  /// Posts the statistics of a single shard, e.g. when each shard runs in its own process with its own [`Twilight`] handler.
+  ///
+  /// # Panics
+  ///
+  /// Panics if the shard ID is not lower than the shard count, rather than posting statistics [Top.gg](https://top.gg) rejects every interval.
+  #[inline(always)]
+  pub fn with_shard(self, shard_id: usize, shard_count: usize) -> Self {
+    self.poster.set_shard(shard_id, shard_count);
+    self
+  }
+
+  /// Whether to hold back every post until the first ready event, instead of posting the partial server counts that guilds streaming in beforehand add up to. This is stricter than [`ClientBuilder::reject_zero_server_count`][crate::ClientBuilder::reject_zero_server_count], which only catches a count of zero. Defaults to `false`.
+  #[inline(always)]
+  pub fn with_suppress_until_ready(self, suppress: bool) -> Self {
+    self.poster.set_suppress_until_ready(suppress);
+    self
+  }
+
+  /// Whether to hold back posts after a ready event until every guild it listed arrived, instead of posting right away. The ready event of bots in many guilds only lists unavailable guilds, which arrive one by one afterwards. Guilds still unavailable after the [`Autoposter`][crate::Autoposter]'s interval are counted as listed by the ready event. Defaults to `false`.
+  #[inline(always)]
+  pub fn with_wait_for_guilds(self, wait: bool) -> Self {
+    self.poster.set_wait_for_guilds(wait);
+    self
+  }
+
+  /// Sets when this handler posts the bot's stats. Defaults to [`PostMode::Hybrid`].
+  #[inline(always)]
+  pub fn with_post_mode(self, mode: PostMode) -> Self {
+    self.poster.set_post_mode(mode);
+    self
+  }
+
+  /// Whether a post that failed (other than being ratelimited or forbidden) is retried on the next guild event rather than a minimum interval later, as it doesn't count as a recent post then. Every guild event retries it until a post succeeds, with the usual retry a minimum interval later as a fallback. Disabled by default.
+  #[inline(always)]
+  pub fn with_retry_on_events(self, retry: bool) -> Self {
+    self.poster.set_retry_on_events(retry);
+    self
+  }
+
+  /// Calls [`Handler::on_significant_change`] whenever the posted server count changes by more than `threshold` between two successful posts. Disabled by default.
+  #[inline(always)]
+  pub fn with_change_threshold(self, threshold: ChangeThreshold) -> Self {
+    self.poster.set_change_threshold(threshold);
+    self
+  }
+
+  /// Sets which events make this handler attempt to post the bot's stats. Defaults to [`PostTriggers::default`], e.g. [`PostTriggers::READY`] alone only posts on ready and then whenever the [`Autoposter`][crate::Autoposter]'s interval allows it.
+  #[must_use]
+  #[inline(always)]
+  pub fn with_triggers(mut self, triggers: PostTriggers) -> Self {
+    self.triggers = triggers;
+    self
+  }
+
+  /// The events that make this handler attempt to post the bot's stats.
+  #[must_use]
+  #[inline(always)]
+  pub fn triggers(&self) -> PostTriggers {
+    self.triggers
   }
 
   /// Handles an entire [twilight](https://twilight.rs) [`Event`] enum.
   pub async fn handle(&self, event: &Event) {
-    match event {
-      Event::Ready(ready) => {
-        {
-          let mut cache = self.cache.lock().await;
-          *cache = ready.guilds.iter().map(|guild| guild.id.get()).collect();
-
-          let mut stats = self.stats.write().await;
-          stats.set_server_count(cache.len());
-        }
-        self.try_post().await;
-      }
-
-      Event::GuildCreate(guild_create) => {
-        {
-          let mut cache = self.cache.lock().await;
-          if cache.insert(guild_create.0.id.get()) {
-            let mut stats = self.stats.write().await;
-            stats.set_server_count(cache.len());
-          }
-        }
-        self.try_post().await;
-      }
-
-      Event::GuildDelete(guild_delete) => {
-        {
-          let mut cache = self.cache.lock().await;
-          if cache.remove(&guild_delete.id.get()) {
-            let mut stats = self.stats.write().await;
-            stats.set_server_count(cache.len());
-          }
-        }
-        self.try_post().await;
-      }
-
-      _ => {}
+    self.poster.update_guilds(event).await;
+
+    if self.triggers.intersects(PostTriggers::of(event)) && !self.poster.is_frozen() {
+      self.poster.try_post().await;
     }
   }
 }
 
+impl GuildEvent for Event {
+  fn ready_guilds(&self) -> Option<Vec<u64>> {
+    match self {
+      Self::Ready(ready) => Some(ready.guilds.iter().map(|guild| guild.id.get()).collect()),
+      _ => None,
+    }
+  }
+
+  fn added(&self) -> Option<u64> {
+    match self {
+      Self::GuildCreate(guild_create) => Some(guild_create.0.id.get()),
+      _ => None,
+    }
+  }
+
+  fn updated(&self) -> Option<u64> {
+    match self {
+      Self::GuildUpdate(guild_update) => Some(guild_update.0.id.get()),
+      _ => None,
+    }
+  }
+
+  fn member_count(&self) -> Option<u64> {
+    match self {
+      Self::GuildUpdate(guild_update) => guild_update.0.member_count,
+      Self::GuildCreate(guild_create) => guild_create
+        .0
+        .member_count
+        .or(guild_create.0.approximate_member_count),
+      _ => None,
+    }
+  }
+
+  fn removed(&self) -> Option<u64> {
+    match self {
+      Self::GuildDelete(guild_delete) => Some(guild_delete.id.get()),
+      _ => None,
+    }
+  }
+}
+
+impl Twilight {
+  /// The amount of guilds the bot was added to since the last successful post, e.g. for charting churn.
+  #[inline(always)]
+  pub fn guilds_added_since_last_post(&self) -> usize {
+    self.poster.guilds_added_since_last_post()
+  }
+
+  /// The amount of guilds the bot was removed from since the last successful post, e.g. for charting churn.
+  #[inline(always)]
+  pub fn guilds_removed_since_last_post(&self) -> usize {
+    self.poster.guilds_removed_since_last_post()
+  }
+}
+
+#[cfg(feature = "twilight-cached")]
+#[cfg_attr(docsrs, doc(cfg(feature = "twilight-cached")))]
+impl Twilight {
+  /// Sets the server count to the amount of guilds in a [twilight](https://twilight.rs) [`InMemoryCache`], then attempts to post it.
+  ///
+  /// Call this after the cache has been updated with each event instead of calling [`handle`][Twilight::handle], so that the bot's guilds aren't tracked twice. Unavailable guilds are counted, as the bot is still in them. Churn, member counts and the ready event are not tracked this way, so [`with_suppress_until_ready`][Twilight::with_suppress_until_ready] and [`with_wait_for_guilds`][Twilight::with_wait_for_guilds] don't apply.
+  ///
+  /// # Examples
+  ///
+  /// Basic usage:
+  ///
+  /// ```rust,no_run
+  /// use topgg::{
+  ///   autoposter::{Twilight, MIN_AUTOPOST_INTERVAL},
+  ///   Client,
+  /// };
+  /// use twilight_cache_inmemory::InMemoryCache;
+  /// use twilight_model::gateway::event::Event;
+  ///
+  /// # async fn run(event: Event) {
+  /// let client = Client::new(std::env::var("TOPGG_TOKEN").unwrap());
+  /// let twilight = Twilight::from_client(&client, MIN_AUTOPOST_INTERVAL);
+  /// let cache = InMemoryCache::new();
+  ///
+  /// cache.update(&event);
+  /// twilight.sync_from_cache(&cache).await;
+  /// # }
+  /// ```
+  pub async fn sync_from_cache(&self, cache: &InMemoryCache) {
+    if self.poster.is_frozen() {
+      return;
+    }
+
+    // guilds that went unavailable after being cached are tracked both as cached and unavailable.
+    let stats = cache.stats();
+    let guild_count = stats.guilds() + stats.unavailable_guilds()
+      - cache
+        .iter()
+        .guilds()
+        .filter(|guild| guild.unavailable())
+        .count();
+
+    {
+      let mut stats = self.poster.stats.write().await;
+      stats.set_server_count(guild_count);
+    }
+    self.poster.try_post().await;
+  }
+}
+
+impl BuiltinHandler for Twilight {
+  #[inline(always)]
+  fn poster(&self) -> &Poster {
+    &self.poster
+  }
+}
+
 impl Handler for Twilight {
+  const KIND: &'static str = "twilight";
+
   #[inline(always)]
   fn stats(&self) -> &SharedStats {
-    &self.stats
+    &self.poster.stats
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{
+    mock::{MockServer, Response},
+    Autoposter, Client,
+  };
+  use twilight_model::gateway::payload::incoming::GuildCreate;
+
+  fn guild_create(guild_id: u64) -> Event {
+    Event::GuildCreate(Box::new(GuildCreate(
+      serde_json::from_value(serde_json::json!({
+        "afk_channel_id": null,
+        "afk_timeout": 300,
+        "application_id": null,
+        "banner": null,
+        "default_message_notifications": 0,
+        "description": null,
+        "discovery_splash": null,
+        "emojis": [],
+        "explicit_content_filter": 0,
+        "features": [],
+        "icon": null,
+        "id": guild_id.to_string(),
+        "large": false,
+        "mfa_level": 0,
+        "name": "guild",
+        "nsfw_level": 0,
+        "owner_id": "1",
+        "preferred_locale": "en-US",
+        "premium_progress_bar_enabled": false,
+        "public_updates_channel_id": null,
+        "roles": [],
+        "rules_channel_id": null,
+        "splash": null,
+        "system_channel_flags": 0,
+        "system_channel_id": null,
+        "vanity_url_code": null,
+        "verification_level": 0,
+      }))
+      .unwrap(),
+    )))
+  }
+
+  // a custom handler composing the built-in one.
+  struct Composite {
+    twilight: Twilight,
+    custom_state: usize,
+  }
+
+  impl Handler for Composite {
+    fn stats(&self) -> &SharedStats {
+      self.twilight.stats()
+    }
+  }
+
+  #[tokio::test]
+  async fn reports_its_kind() {
+    let client = Client::new(String::from("token"));
+
+    assert_eq!(
+      Autoposter::twilight(&client, MIN_AUTOPOST_INTERVAL).handler_kind(),
+      "twilight"
+    );
+  }
+
+  #[tokio::test]
+  async fn can_be_embedded_in_custom_handlers() {
+    let client = Client::new(String::from("token"));
+    let autoposter = Autoposter::new(
+      Composite {
+        twilight: Twilight::from_client(&client, MIN_AUTOPOST_INTERVAL),
+        custom_state: 0,
+      },
+      MIN_AUTOPOST_INTERVAL,
+    );
+
+    autoposter.stats().write().await.set_server_count(42);
+
+    assert_eq!(autoposter.custom_state, 0);
+    assert_eq!(
+      autoposter
+        .twilight
+        .stats()
+        .stats
+        .read()
+        .await
+        .server_count(),
+      Some(42)
+    );
+  }
+
+  #[tokio::test]
+  async fn posts_the_configured_shard() {
+    let server = MockServer::start(|_| Response::new(200));
+    let twilight = Twilight::from_client(&server.client(), MIN_AUTOPOST_INTERVAL).with_shard(1, 2);
+
+    twilight.handle(&guild_create(1)).await;
+
+    assert_eq!(
+      server.requests()[0].body,
+      br#"{"shard_count":2,"server_count":1,"shard_id":1}"#
+    );
+  }
+
+  #[test]
+  #[should_panic(expected = "The shard ID must be lower than the shard count.")]
+  fn rejects_shard_ids_out_of_range() {
+    let client = Client::new(String::from("token"));
+    let _ = Twilight::from_client(&client, MIN_AUTOPOST_INTERVAL).with_shard(2, 2);
+  }
+
+  #[tokio::test]
+  async fn disabled_triggers_dont_post() {
+    let server = MockServer::start(|_| Response::new(200));
+    let twilight = Twilight::from_client(&server.client(), MIN_AUTOPOST_INTERVAL)
+      .with_triggers(PostTriggers::READY);
+
+    twilight.handle(&guild_create(1)).await;
+
+    // the server count is kept up to date, it just isn't posted.
+    assert_eq!(twilight.stats().stats.read().await.server_count(), Some(1));
+    assert!(server.requests().is_empty());
+
+    let twilight = Twilight::from_client(&server.client(), MIN_AUTOPOST_INTERVAL);
+
+    twilight.handle(&guild_create(1)).await;
+    assert_eq!(server.requests().len(), 1);
+  }
+
+  #[tokio::test]
+  async fn resumes_post_by_default() {
+    let server = MockServer::start(|_| Response::new(200));
+    let twilight = Twilight::from_client(&server.client(), MIN_AUTOPOST_INTERVAL);
+
+    twilight.stats().write().await.set_server_count(1);
+    twilight.handle(&Event::Resumed).await;
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].body, br#"{"server_count":1}"#);
+  }
+
+  #[cfg(feature = "twilight-cached")]
+  #[tokio::test]
+  async fn server_counts_are_synced_from_the_cache() {
+    use twilight_model::{
+      gateway::payload::incoming::{GuildDelete, UnavailableGuild},
+      id::Id,
+    };
+
+    let server = MockServer::start(|_| Response::new(200));
+    let twilight = Twilight::from_client(&server.client(), MIN_AUTOPOST_INTERVAL);
+    let cache = InMemoryCache::new();
+    let unavailable = |guild_id| {
+      Event::UnavailableGuild(UnavailableGuild {
+        id: Id::new(guild_id),
+      })
+    };
+
+    // guild 4 hasn't arrived yet, and guild 3 went unavailable after arriving.
+    for guild_id in 1..=4 {
+      cache.update(&unavailable(guild_id));
+    }
+    for guild_id in 1..=3 {
+      cache.update(&guild_create(guild_id));
+    }
+    cache.update(&unavailable(3));
+
+    twilight.sync_from_cache(&cache).await;
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].body, br#"{"server_count":4}"#);
+
+    cache.update(&Event::GuildDelete(GuildDelete {
+      id: Id::new(2),
+      unavailable: false,
+    }));
+    twilight.sync_from_cache(&cache).await;
+
+    assert_eq!(twilight.stats().stats.read().await.server_count(), Some(3));
   }
 }
@@ -1,6 +1,6 @@
-use crate::{snowflake, util};
+use crate::{snowflake, util, Snowflake};
 use chrono::{DateTime, Utc};
-use serde::{Deserialize, Deserializer, Serialize};
+use serde::{de::IgnoredAny, Deserialize, Deserializer, Serialize};
 
 #[inline(always)]
 pub(crate) fn deserialize_support_server<'de, D>(
@@ -13,6 +13,42 @@ where
     .map(|inner| inner.map(|support| format!("https://discord.com/invite/{support}")))
 }
 
+/// A struct representing a Discord bot's reviews on [Top.gg](https://top.gg).
+#[must_use]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Deserialize)]
+pub struct Reviews {
+  /// The average review score of this Discord bot, from 0 to 5. Zero if it has no reviews.
+  #[serde(default, rename = "averageScore")]
+  pub average_score: f64,
+
+  /// The amount of reviews this Discord bot has.
+  #[serde(default)]
+  pub count: usize,
+}
+
+/// The social links of a Discord bot listed on [Top.gg](https://top.gg), borrowed from it. (See [`Bot::social`])
+#[must_use]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Social<'a> {
+  /// The website URL of this Discord bot.
+  pub website: Option<&'a str>,
+
+  /// The link to this Discord bot's GitHub repository.
+  pub github: Option<&'a str>,
+
+  /// The support server invite URL of this Discord bot.
+  pub support: Option<&'a str>,
+}
+
+impl Social<'_> {
+  /// Whether this Discord bot has no social links at all.
+  #[must_use]
+  #[inline(always)]
+  pub const fn is_empty(&self) -> bool {
+    self.website.is_none() && self.github.is_none() && self.support.is_none()
+  }
+}
+
 util::debug_struct! {
   /// A struct representing a Discord Bot listed on [Top.gg](https://top.gg).
   #[must_use]
@@ -81,20 +117,26 @@ util::debug_struct! {
       is_certified: bool,
 
       /// A list of this Discord bot's shards.
-      #[serde(default, deserialize_with = "util::deserialize_default")]
+      #[serde(default, deserialize_with = "util::deserialize_counts")]
       shards: Vec<usize>,
 
-      /// The amount of upvotes this Discord bot has.
-      #[serde(rename = "points")]
+      /// The amount of upvotes (points) this Discord bot has of all time.
+      #[serde(rename = "points", deserialize_with = "util::deserialize_count")]
       votes: usize,
 
-      /// The amount of upvotes this Discord bot has this month.
-      #[serde(rename = "monthlyPoints")]
+      /// The amount of upvotes (points) this Discord bot has this month.
+      ///
+      /// [Top.gg](https://top.gg)'s API only serves the current amounts, not their history, so fetch this Discord bot periodically and record them to chart its growth.
+      #[serde(rename = "monthlyPoints", deserialize_with = "util::deserialize_count")]
       monthly_votes: usize,
 
       /// The support server invite URL of this Discord bot.
       #[serde(default, deserialize_with = "deserialize_support_server")]
       support: Option<String>,
+
+      /// The reviews of this Discord bot.
+      #[serde(default, deserialize_with = "util::deserialize_default")]
+      reviews: Reviews,
     }
 
     private {
@@ -104,10 +146,27 @@ util::debug_struct! {
       #[serde(default, deserialize_with = "util::deserialize_optional_string")]
       invite: Option<String>,
 
+      #[serde(default, deserialize_with = "util::deserialize_optional_count")]
       shard_count: Option<usize>,
 
       #[serde(default, deserialize_with = "util::deserialize_optional_string")]
       vanity: Option<String>,
+
+      // sent by Top.gg, but either deprecated or redundant. listed so that strict clients accept them.
+      #[serde(default, rename = "defAvatar")]
+      _default_avatar: IgnoredAny,
+
+      #[serde(default, rename = "lib")]
+      _library: IgnoredAny,
+
+      #[serde(default, rename = "clientid")]
+      _client_id: IgnoredAny,
+
+      #[serde(default, rename = "server_count")]
+      _server_count: IgnoredAny,
+
+      #[serde(default, rename = "donatebotguildid")]
+      _donatebot_guild_id: IgnoredAny,
     }
 
     getters(self) {
@@ -127,9 +186,9 @@ util::debug_struct! {
         util::get_avatar(&self.avatar, self.id)
       }
 
-      /// The invite URL of this Discord bot.
+      /// The invite URL of this Discord bot. This is its custom invite if it has one, otherwise a default Discord OAuth invite built from its ID.
       #[must_use]
-      invite: String => {
+      invite_url: String => {
         match &self.invite {
           Some(inv) => inv.to_owned(),
           _ => format!(
@@ -162,6 +221,10 @@ util::debug_struct! {
 util::debug_struct! {
   /// A struct representing a Discord bot's statistics.
   ///
+  /// **NOTE:** [Top.gg](https://top.gg) only accepts server and shard counts when posting statistics. Votes and points can't be posted, and are read from [`Bot::monthly_votes`] and [`Bot::votes`] instead.
+  ///
+  /// It (de)serializes with [Top.gg](https://top.gg)'s field names, e.g. to persist the last posted statistics to disk or to a database.
+  ///
   /// # Examples
   ///
   /// Solely from a server count:
@@ -188,22 +251,22 @@ util::debug_struct! {
   /// use topgg::Stats;
   ///
   /// // the shard posting this data has 456 servers.
-  /// let _stats = Stats::from_shards([123, 456, 789], Some(1));
+  /// let _stats = Stats::from_shards([123usize, 456, 789], Some(1));
   /// ```
   #[must_use]
   #[derive(Clone, Serialize, Deserialize)]
   Stats {
     protected {
-      #[serde(skip_serializing_if = "Option::is_none")]
+      #[serde(default, skip_serializing_if = "Option::is_none", deserialize_with = "util::deserialize_optional_count")]
       shard_count: Option<usize>,
-      #[serde(skip_serializing_if = "Option::is_none")]
+      #[serde(default, skip_serializing_if = "Option::is_none", deserialize_with = "util::deserialize_optional_count")]
       server_count: Option<usize>,
     }
 
     private {
-      #[serde(default, skip_serializing_if = "Option::is_none", deserialize_with = "util::deserialize_default")]
+      #[serde(default, skip_serializing_if = "Option::is_none", deserialize_with = "util::deserialize_optional_counts")]
       shards: Option<Vec<usize>>,
-      #[serde(default, skip_serializing_if = "Option::is_none", deserialize_with = "util::deserialize_default")]
+      #[serde(default, skip_serializing_if = "Option::is_none", deserialize_with = "util::deserialize_optional_count")]
       shard_id: Option<usize>,
     }
 
@@ -218,17 +281,16 @@ util::debug_struct! {
         }
       }
 
-      /// The amount of shards this Discord bot has.
+      /// The amount of shards this Discord bot has. Falls back to the length of [`shards`][Stats::shards] if no shard count was given, and is [`None`] if neither was.
       #[must_use]
       #[inline(always)]
-      shard_count: usize => {
-        self.shard_count.unwrap_or(match self.shards {
-          Some(ref shards) => shards.len(),
-          None => 0,
-        })
+      shard_count: Option<usize> => {
+        self
+          .shard_count
+          .or_else(|| self.shards.as_ref().map(Vec::len))
       }
 
-      /// The amount of servers this bot is in. `None` if such information is publy unavailable.
+      /// The amount of servers this bot is in. `None` if such information is publicly unavailable.
       #[must_use]
       server_count: Option<usize> => {
         self.server_count.or_else(|| {
@@ -245,6 +307,75 @@ util::debug_struct! {
   }
 }
 
+impl Bot {
+  /// Whether a Discord user is one of this Discord bot's owners or not, e.g. for gating owner-only commands.
+  ///
+  /// # Panics
+  ///
+  /// Panics if the ID argument is a string but not numeric.
+  #[must_use]
+  #[inline(always)]
+  pub fn is_owner<I>(&self, id: I) -> bool
+  where
+    I: Snowflake,
+  {
+    self.owners.contains(&id.as_snowflake())
+  }
+
+  /// The social links of this Discord bot, grouped together, e.g. for rendering them on a profile.
+  #[inline(always)]
+  pub fn social(&self) -> Social<'_> {
+    Social {
+      website: self.website.as_deref(),
+      github: self.github.as_deref(),
+      support: self.support.as_deref(),
+    }
+  }
+
+  /// Whether this Discord bot has a specific tag or not. The comparison is case-insensitive.
+  #[must_use]
+  #[inline(always)]
+  pub fn has_tag(&self, tag: &str) -> bool {
+    self.tags.iter().any(|t| t.eq_ignore_ascii_case(tag))
+  }
+
+  /// The invite URL of this Discord bot. This is the same as [`invite_url`][Bot::invite_url].
+  #[must_use]
+  #[inline(always)]
+  pub fn invite(&self) -> String {
+    self.invite_url()
+  }
+}
+
+/// A trait that represents any unsigned integer that can be interpreted as the server count of a shard, either by value or by reference. (See [`Stats::from_shards`])
+pub trait ServerCount {
+  /// The method that converts this value to a [`usize`], or [`None`] if it does not fit in one.
+  fn as_server_count(&self) -> Option<usize>;
+}
+
+macro_rules! impl_server_count(
+  ($($t:ty),+) => {$(
+    impl ServerCount for $t {
+      #[inline(always)]
+      fn as_server_count(&self) -> Option<usize> {
+        usize::try_from(*self).ok()
+      }
+    }
+  )+}
+);
+
+impl_server_count!(u8, u16, u32, u64, u128, usize);
+
+impl<T> ServerCount for &T
+where
+  T: ServerCount + ?Sized,
+{
+  #[inline(always)]
+  fn as_server_count(&self) -> Option<usize> {
+    (**self).as_server_count()
+  }
+}
+
 impl Stats {
   /// Creates a [`Stats`] struct from the cache of a serenity [`Context`][serenity::client::Context].
   #[inline(always)]
@@ -257,6 +388,17 @@ impl Stats {
     )
   }
 
+  #[inline(always)]
+  #[cfg(feature = "autoposter")]
+  pub(crate) const fn empty() -> Self {
+    Self {
+      server_count: None,
+      shard_count: None,
+      shards: None,
+      shard_id: None,
+    }
+  }
+
   /// Creates a [`Stats`] struct based on total server and optionally, shard count data.
   pub const fn from_count(server_count: usize, shard_count: Option<usize>) -> Self {
     Self {
@@ -269,9 +411,13 @@ impl Stats {
 
   /// Creates a [`Stats`] struct based on an array of server count per shard and optionally the index (to the array) of shard posting this data.
   ///
+  /// The server counts can be of any unsigned integer type, by value or by reference, e.g. a [`Vec<u16>`][Vec] or a slice of [`u32`]s, so that a negative count can't be passed in the first place. (See [`ServerCount`])
+  ///
   /// # Panics
   ///
-  /// Panics if the shard_index argument is [`Some`] yet it's out of range of the `shards` array.
+  /// Panics if any of the following conditions are met:
+  /// - The shard_index argument is [`Some`] yet it's out of range of the `shards` array.
+  /// - A server count does not fit in a [`usize`].
   ///
   /// # Examples
   ///
@@ -281,17 +427,31 @@ impl Stats {
   /// use topgg::Stats;
   ///
   /// // the shard posting this data has 456 servers.
-  /// let _stats = Stats::from_shards([123, 456, 789], Some(1));
+  /// let _stats = Stats::from_shards([123usize, 456, 789], Some(1));
+  /// ```
+  ///
+  /// From a slice of other integers:
+  ///
+  /// ```rust,no_run
+  /// use topgg::Stats;
+  ///
+  /// let shards: &[u32] = &[123, 456, 789];
+  /// let _stats = Stats::from_shards(shards, None);
   /// ```
   pub fn from_shards<A>(shards: A, shard_index: Option<usize>) -> Self
   where
-    A: IntoIterator<Item = usize>,
+    A: IntoIterator,
+    A::Item: ServerCount,
   {
     let mut total_server_count = 0;
     let shards = shards.into_iter();
     let mut shards_list = Vec::with_capacity(shards.size_hint().0);
 
     for server_count in shards {
+      let server_count = server_count
+        .as_server_count()
+        .expect("Invalid shard server count.");
+
       total_server_count += server_count;
       shards_list.push(server_count);
     }
@@ -307,6 +467,147 @@ impl Stats {
       shard_id: shard_index,
     }
   }
+
+  /// Omits an empty [`shards`][Stats::shards] array along with a shard count of zero, which [Top.gg](https://top.gg) could otherwise read as the bot having zero shards. Posted statistics are always normalized this way.
+  ///
+  /// # Examples
+  ///
+  /// Basic usage:
+  ///
+  /// ```rust,no_run
+  /// use topgg::Stats;
+  ///
+  /// let mut stats = Stats::from_shards(Vec::<usize>::new(), None);
+  /// stats.normalize();
+  ///
+  /// assert_eq!(stats.shard_count(), None);
+  /// ```
+  pub fn normalize(&mut self) {
+    if self.shards.as_ref().is_some_and(Vec::is_empty) {
+      self.shards = None;
+    }
+
+    if self.shard_count == Some(0) {
+      self.shard_count = None;
+    }
+  }
+
+  #[cfg(feature = "autoposter")]
+  #[inline(always)]
+  pub(crate) fn set_shard(&mut self, shard_id: usize, shard_count: usize) {
+    self.shard_id = Some(shard_id);
+    self.shard_count = Some(shard_count);
+  }
+
+  /// Merges another [`Stats`] struct into this one, e.g. for a coordinator assembling the stats reported by each shard process.
+  ///
+  /// The server counts of each shard are combined by shard ID, using the [`shards`][Stats::shards] array of each side, or the single shard ID of stats created from a [`ShardStat`]. If both sides have a server count for the same shard, the one of `other` wins, as it is considered more recent. Server counts without any shard information are added to the total. The merged stats no longer belong to a single shard.
+  ///
+  /// # Examples
+  ///
+  /// Basic usage:
+  ///
+  /// ```rust,no_run
+  /// use topgg::{ShardStat, Stats};
+  ///
+  /// let mut stats = Stats::from(ShardStat { shard_id: 0, server_count: 123 });
+  /// stats.merge(&Stats::from(ShardStat { shard_id: 1, server_count: 456 }));
+  ///
+  /// assert_eq!(stats.server_count(), Some(579));
+  /// assert_eq!(stats.shards(), &[123, 456]);
+  /// ```
+  pub fn merge(&mut self, other: &Stats) {
+    let (mut shards, mut unattributed) = self.shard_parts();
+    let (other_shards, other_unattributed) = other.shard_parts();
+
+    if shards.len() < other_shards.len() {
+      shards.resize(other_shards.len(), None);
+    }
+
+    for (shard, server_count) in shards.iter_mut().zip(other_shards) {
+      if server_count.is_some() {
+        *shard = server_count;
+      }
+    }
+
+    unattributed += other_unattributed;
+
+    let has_server_count = self.server_count().is_some() || other.server_count().is_some();
+    let shard_count = self.shard_count.max(other.shard_count);
+
+    if shards.is_empty() {
+      self.server_count = has_server_count.then_some(unattributed);
+      self.shard_count = shard_count;
+      self.shards = None;
+    } else {
+      let shards: Vec<usize> = shards.into_iter().map(Option::unwrap_or_default).collect();
+
+      self.server_count = Some(shards.iter().sum::<usize>() + unattributed);
+      self.shard_count = Some(shard_count.unwrap_or(0).max(shards.len()));
+      self.shards = Some(shards);
+    }
+
+    self.shard_id = None;
+  }
+
+  // the server count of each shard indexed by shard ID if known, along with the servers not attributed to any shard.
+  fn shard_parts(&self) -> (Vec<Option<usize>>, usize) {
+    match (&self.shards, self.shard_id) {
+      (Some(shards), _) => {
+        let total: usize = shards.iter().sum();
+
+        (
+          shards.iter().copied().map(Some).collect(),
+          self.server_count.unwrap_or(total).saturating_sub(total),
+        )
+      }
+
+      (None, Some(shard_id)) => {
+        let mut shards = vec![None; shard_id + 1];
+        shards[shard_id] = self.server_count;
+
+        (shards, 0)
+      }
+
+      (None, None) => (Vec::new(), self.server_count.unwrap_or(0)),
+    }
+  }
+}
+
+/// A struct representing a single shard's server count, posted alongside the other shards by a coordinator. (See [`Client::post_shard_stats`][crate::Client::post_shard_stats])
+#[must_use]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ShardStat {
+  /// The ID (index) of this shard, starting from zero.
+  pub shard_id: usize,
+
+  /// The amount of servers this shard is in.
+  pub server_count: usize,
+}
+
+impl ShardStat {
+  #[inline(always)]
+  pub(crate) const fn into_stats(self, shard_count: usize) -> Stats {
+    Stats {
+      server_count: Some(self.server_count),
+      shard_count: Some(shard_count),
+      shards: None,
+      shard_id: Some(self.shard_id),
+    }
+  }
+}
+
+/// Creates a [`Stats`] struct from a single shard's server count.
+impl From<ShardStat> for Stats {
+  #[inline(always)]
+  fn from(shard: ShardStat) -> Self {
+    Self {
+      server_count: Some(shard.server_count),
+      shard_count: None,
+      shards: None,
+      shard_id: Some(shard.shard_id),
+    }
+  }
 }
 
 /// Creates a [`Stats`] struct solely from a server count.
@@ -317,7 +618,568 @@ impl From<usize> for Stats {
   }
 }
 
+/// The JSON schema [`Stats`] are posted in. Only useful when posting to a [Top.gg](https://top.gg)-compatible API through [`ClientBuilder::base_url`][crate::ClientBuilder::base_url].
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```rust,no_run
+/// use topgg::{Client, PostSchema};
+///
+/// let _client = Client::builder(std::env::var("TOPGG_TOKEN").unwrap())
+///   .base_url("https://example.com/api")
+///   .post_schema(PostSchema::Custom {
+///     server_count: "guildCount",
+///     shard_count: "shardCount",
+///     shards: "shards",
+///     shard_id: "shardId",
+///   })
+///   .build()
+///   .unwrap();
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum PostSchema {
+  /// [Top.gg](https://top.gg)'s own schema, e.g. `{"server_count": 12345}`. This is the default.
+  #[default]
+  TopGg,
+
+  /// [Top.gg](https://top.gg)'s schema with every field renamed.
+  Custom {
+    /// The name of the server count field.
+    server_count: &'static str,
+
+    /// The name of the shard count field.
+    shard_count: &'static str,
+
+    /// The name of the field containing the server count of each shard.
+    shards: &'static str,
+
+    /// The name of the field containing the ID of the shard posting the stats.
+    shard_id: &'static str,
+  },
+}
+
+impl PostSchema {
+  // the stats are normalized and the shard count is derived from the shards if missing, so that the payload is consistent.
+  pub(crate) fn serialize(&self, stats: &Stats) -> Vec<u8> {
+    let mut stats = stats.clone();
+    stats.normalize();
+    stats.shard_count = stats
+      .shard_count
+      .or_else(|| stats.shards.as_ref().map(Vec::len));
+
+    match *self {
+      Self::TopGg => serde_json::to_vec(&stats).unwrap(),
+
+      Self::Custom {
+        server_count,
+        shard_count,
+        shards,
+        shard_id,
+      } => {
+        let mut output = serde_json::Map::new();
+
+        if let Some(count) = stats.shard_count {
+          output.insert(shard_count.to_owned(), count.into());
+        }
+
+        if let Some(count) = stats.server_count {
+          output.insert(server_count.to_owned(), count.into());
+        }
+
+        if let Some(counts) = stats.shards {
+          output.insert(shards.to_owned(), counts.into());
+        }
+
+        if let Some(id) = stats.shard_id {
+          output.insert(shard_id.to_owned(), id.into());
+        }
+
+        serde_json::to_vec(&output).unwrap()
+      }
+    }
+  }
+}
+
+/// A query for listed Discord bots. (See [`Client::get_bots`][crate::Client::get_bots])
+///
+/// Filters are combined with [Top.gg](https://top.gg)'s `field: value` search syntax, so every filter must match.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```rust,no_run
+/// use topgg::BotsQuery;
+///
+/// let query = BotsQuery::new()
+///   .tag("music")
+///   .sort_by_monthly_votes()
+///   .limit(50);
+/// ```
+#[must_use]
+#[derive(Clone, Debug, Default)]
+pub struct BotsQuery {
+  filters: Vec<(&'static str, String)>,
+  sort: Option<&'static str>,
+  limit: Option<u16>,
+  offset: Option<u16>,
+}
+
+impl BotsQuery {
+  /// Creates an empty [`BotsQuery`], which matches every listed Discord bot.
+  #[inline(always)]
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Only matches Discord bots whose username contains `username`.
+  #[inline(always)]
+  pub fn username(self, username: &str) -> Self {
+    self.filter("username", username)
+  }
+
+  /// Only matches Discord bots in a category, e.g. `"music"`.
+  #[inline(always)]
+  pub fn tag(self, tag: &str) -> Self {
+    self.filter("tags", tag)
+  }
+
+  /// Sorts the results by monthly votes.
+  #[inline(always)]
+  pub fn sort_by_monthly_votes(mut self) -> Self {
+    self.sort = Some("monthlyPoints");
+    self
+  }
+
+  /// Sorts the results by approval date.
+  #[inline(always)]
+  pub fn sort_by_approval_date(mut self) -> Self {
+    self.sort = Some("date");
+    self
+  }
+
+  /// Sets the maximum amount of results, which is capped at 500.
+  #[inline(always)]
+  pub fn limit(mut self, limit: u16) -> Self {
+    self.limit = Some(limit.min(500));
+    self
+  }
+
+  /// Skips the first `offset` results, which is capped at 499.
+  #[inline(always)]
+  pub fn offset(mut self, offset: u16) -> Self {
+    self.offset = Some(offset.min(499));
+    self
+  }
+
+  fn filter(mut self, field: &'static str, value: &str) -> Self {
+    self.filters.push((field, value.trim().to_owned()));
+    self
+  }
+
+  pub(crate) fn path(&self) -> String {
+    let mut params = Vec::new();
+
+    if !self.filters.is_empty() {
+      let search: Vec<_> = self
+        .filters
+        .iter()
+        .map(|(field, value)| format!("{field}: {value}"))
+        .collect();
+
+      params.push(format!("search={}", urlencoding::encode(&search.join(" "))));
+    }
+
+    if let Some(sort) = self.sort {
+      params.push(format!("sort={sort}"));
+    }
+
+    if let Some(limit) = self.limit {
+      params.push(format!("limit={limit}"));
+    }
+
+    if let Some(offset) = self.offset {
+      params.push(format!("offset={offset}"));
+    }
+
+    if params.is_empty() {
+      String::from("/bots")
+    } else {
+      format!("/bots?{}", params.join("&"))
+    }
+  }
+}
+
+#[derive(Deserialize)]
+pub(crate) struct Bots {
+  pub(crate) results: Vec<Bot>,
+  pub(crate) total: usize,
+
+  // the pagination of the request, echoed back.
+  #[serde(default, rename = "limit")]
+  _limit: IgnoredAny,
+
+  #[serde(default, rename = "offset")]
+  _offset: IgnoredAny,
+
+  #[serde(default, rename = "count")]
+  _count: IgnoredAny,
+}
+
 #[derive(Deserialize)]
 pub(crate) struct IsWeekend {
   pub(crate) is_weekend: bool,
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::mock;
+
+  #[test]
+  fn bots_queries_are_encoded() {
+    assert_eq!(BotsQuery::new().path(), "/bots");
+    assert_eq!(
+      BotsQuery::new()
+        .tag("music")
+        .username("luca")
+        .sort_by_monthly_votes()
+        .limit(1000)
+        .offset(20)
+        .path(),
+      "/bots?search=tags%3A%20music%20username%3A%20luca&sort=monthlyPoints&limit=500&offset=20"
+    );
+    assert_eq!(
+      BotsQuery::new().tag(" role play ").path(),
+      "/bots?search=tags%3A%20role%20play"
+    );
+  }
+
+  fn bot(extra: &str) -> Bot {
+    serde_json::from_str(&mock::bot_json(extra)).unwrap()
+  }
+
+  #[test]
+  fn social() {
+    assert!(bot("").social().is_empty());
+
+    let bot = bot(r#", "github": "https://github.com/top-gg/rust-sdk", "website": """#);
+    let social = bot.social();
+
+    assert_eq!(social.github, Some("https://github.com/top-gg/rust-sdk"));
+    assert_eq!(social.website, None);
+    assert_eq!(social.support, None);
+    assert!(!social.is_empty());
+  }
+
+  #[test]
+  fn points() {
+    let bot = bot("");
+
+    assert_eq!(bot.votes, 397);
+    assert_eq!(bot.monthly_votes, 19);
+  }
+
+  #[test]
+  fn counts_may_be_numeric_strings() {
+    let json =
+      mock::bot_json(r#", "shards": ["20", 22], "shard_count": " 2""#).replace("397", r#""397""#);
+    let bot: Bot = serde_json::from_str(&json).unwrap();
+
+    assert_eq!(bot.votes, 397);
+    assert_eq!(bot.monthly_votes, 19);
+    assert_eq!(bot.shards, [20, 22]);
+    assert_eq!(bot.shard_count(), 2);
+
+    for json in [
+      r#"{"server_count":42,"shard_count":2,"shards":[20,22],"shard_id":1}"#,
+      r#"{"server_count":"42","shard_count":"2","shards":["20","22"],"shard_id":"1"}"#,
+    ] {
+      let stats: Stats = serde_json::from_str(json).unwrap();
+
+      assert_eq!(stats.server_count(), Some(42));
+      assert_eq!(stats.shard_count(), Some(2));
+      assert_eq!(stats.shards(), [20, 22]);
+    }
+
+    assert!(serde_json::from_str::<Stats>(r#"{"server_count":"many"}"#).is_err());
+  }
+
+  #[test]
+  fn invite_url_prefers_custom_invite() {
+    let bot = bot(r#", "invite": "https://example.com/invite""#);
+    assert_eq!(bot.invite_url(), "https://example.com/invite");
+  }
+
+  #[test]
+  fn invite_url_defaults_to_oauth() {
+    assert_eq!(
+      bot("").invite_url(),
+      "https://discord.com/oauth2/authorize?scope=bot&client_id=264811613708746752"
+    );
+    assert_eq!(bot(r#", "invite": """#).invite_url(), bot("").invite_url());
+  }
+
+  #[test]
+  fn owners() {
+    let bot = bot("");
+    assert_eq!(bot.owners, [129908908096487424]);
+    assert!(bot.is_owner(129908908096487424));
+    assert!(bot.is_owner("129908908096487424"));
+    assert!(!bot.is_owner(264811613708746752));
+
+    let bot: Bot = serde_json::from_str(&mock::bot_json("").replace(
+      r#""owners": ["129908908096487424"]"#,
+      r#""owners": ["1", 2, " 3 ", "not an id", null, -4]"#,
+    ))
+    .unwrap();
+    assert_eq!(bot.owners, [1, 2, 3]);
+  }
+
+  #[test]
+  fn unknown_fields_are_ignored() {
+    let bot = serde_json::from_str::<Bot>(&mock::bot_json(r#", "someNewField": true"#));
+    let stats = serde_json::from_str::<Stats>(r#"{"server_count": 2, "someNewField": true}"#);
+
+    assert_eq!(bot.unwrap().username, "Luca");
+    assert_eq!(stats.unwrap().server_count(), Some(2));
+  }
+
+  #[test]
+  fn real_responses_are_strictly_valid() {
+    let bot: Bot = util::parse_json_slice_strict(mock::REAL_BOT_JSON.as_bytes()).unwrap();
+
+    assert_eq!(bot.username, "Luca");
+    assert_eq!(bot.shard_count(), 1);
+    assert_eq!(bot.reviews.count, 12);
+    assert!(bot
+      .invite_url()
+      .starts_with("https://discord.com/oauth2/authorize"));
+
+    let page = format!(
+      r#"{{"results":[{}],"limit":1,"offset":0,"count":1,"total":1}}"#,
+      mock::REAL_BOT_JSON
+    );
+    let bots: Bots = util::parse_json_slice_strict(page.as_bytes()).unwrap();
+    assert_eq!(bots.results.len(), 1);
+
+    let user: crate::user::User = util::parse_json_slice_strict(
+      br#"{
+        "discriminator": "0001",
+        "avatar": "a_1241439d430def25c100dd28add2d42f",
+        "id": "140862798832861184",
+        "username": "Xetera",
+        "defAvatar": "322c936a8c8be1b803cd94861bdfa868",
+        "admin": true,
+        "webMod": true,
+        "mod": true,
+        "certifiedDev": false,
+        "supporter": false,
+        "social": {}
+      }"#,
+    )
+    .unwrap();
+    assert_eq!(user.username, "Xetera");
+  }
+
+  #[test]
+  fn tags() {
+    for extra in ["", r#", "tags": []"#, r#", "tags": null"#] {
+      let untagged = bot(extra);
+      assert!(untagged.tags.is_empty());
+      assert!(!untagged.has_tag("Moderation"));
+    }
+
+    let bot = bot(r#", "tags": ["Moderation", "Utility"]"#);
+    assert_eq!(bot.tags, ["Moderation", "Utility"]);
+    assert!(bot.has_tag("moderation"));
+    assert!(bot.has_tag("UTILITY"));
+    assert!(!bot.has_tag("Music"));
+  }
+
+  #[test]
+  fn stats_getters() {
+    let stats = Stats::from(12345);
+    assert_eq!(stats.server_count(), Some(12345));
+    assert_eq!(stats.shard_count(), None);
+
+    let stats = Stats::from_count(12345, Some(10));
+    assert_eq!(stats.server_count(), Some(12345));
+    assert_eq!(stats.shard_count(), Some(10));
+
+    let stats = Stats::from_shards([123usize, 456, 789], Some(1));
+    assert_eq!(stats.server_count(), Some(1368));
+    assert_eq!(stats.shard_count(), Some(3));
+    assert_eq!(stats.shards(), &[123, 456, 789]);
+  }
+
+  #[test]
+  fn stats_from_shards_of_any_integer() {
+    let stats = Stats::from_shards(vec![100u16, 200, 300], Some(2));
+    assert_eq!(stats.server_count(), Some(600));
+    assert_eq!(stats.shard_count(), Some(3));
+
+    let shards: &[u32] = &[123, 456];
+    let stats = Stats::from_shards(shards, None);
+    assert_eq!(stats.server_count(), Some(579));
+    assert_eq!(stats.shards(), &[123, 456]);
+
+    let shards = vec![7usize, 8];
+    assert_eq!(Stats::from_shards(&shards, Some(0)).shards(), &[7, 8]);
+
+    assert_eq!(
+      Stats::from_shards([1u8, 2], None).server_count(),
+      Stats::from_shards([1u64, 2], None).server_count()
+    );
+  }
+
+  #[test]
+  fn stats_merge() {
+    let shard = |shard_id, server_count| {
+      Stats::from(ShardStat {
+        shard_id,
+        server_count,
+      })
+    };
+
+    let mut stats = shard(0, 10);
+    stats.merge(&shard(1, 20));
+    assert_eq!(stats.server_count(), Some(30));
+    assert_eq!(stats.shards(), &[10, 20]);
+    assert_eq!(stats.shard_count(), Some(2));
+
+    // the latest count of a shard wins.
+    stats.merge(&shard(1, 25));
+    assert_eq!(stats.shards(), &[10, 25]);
+
+    stats.merge(&shard(3, 5));
+    assert_eq!(stats.shards(), &[10, 25, 0, 5]);
+    assert_eq!(stats.server_count(), Some(40));
+    assert_eq!(stats.shard_count(), Some(4));
+
+    stats.merge(&Stats::from_shards([1usize, 2], None));
+    assert_eq!(stats.shards(), &[1, 2, 0, 5]);
+
+    stats.merge(&Stats::from(100));
+    assert_eq!(stats.server_count(), Some(108));
+    assert_eq!(stats.shards(), &[1, 2, 0, 5]);
+
+    let mut stats = Stats::from(100);
+    stats.merge(&Stats::from_count(50, Some(2)));
+    assert_eq!(stats.server_count(), Some(150));
+    assert_eq!(stats.shard_count(), Some(2));
+    assert!(stats.shards().is_empty());
+  }
+
+  #[test]
+  fn post_schemas() {
+    let serialize = |schema: PostSchema, stats: &Stats| {
+      serde_json::from_slice::<serde_json::Value>(&schema.serialize(stats)).unwrap()
+    };
+
+    let custom = PostSchema::Custom {
+      server_count: "guildCount",
+      shard_count: "shardCount",
+      shards: "shards",
+      shard_id: "shardId",
+    };
+
+    let stats = Stats::from_count(12345, Some(10));
+    assert_eq!(
+      serialize(PostSchema::default(), &stats),
+      serde_json::json!({ "server_count": 12345, "shard_count": 10 })
+    );
+    assert_eq!(
+      serialize(custom.clone(), &stats),
+      serde_json::json!({ "guildCount": 12345, "shardCount": 10 })
+    );
+
+    let stats = Stats::from_shards([123usize, 456], Some(1));
+    assert_eq!(
+      serialize(PostSchema::TopGg, &stats),
+      serde_json::json!({ "server_count": 579, "shard_count": 2, "shards": [123, 456], "shard_id": 1 })
+    );
+    assert_eq!(
+      serialize(custom, &stats),
+      serde_json::json!({ "guildCount": 579, "shardCount": 2, "shards": [123, 456], "shardId": 1 })
+    );
+  }
+
+  #[test]
+  fn posted_shard_count_is_derived_from_shards() {
+    let stats: Stats = serde_json::from_str(r#"{"shards": [123, 456]}"#).unwrap();
+
+    assert_eq!(
+      PostSchema::TopGg.serialize(&stats),
+      br#"{"shard_count":2,"shards":[123,456]}"#
+    );
+
+    // an explicit shard count is kept as-is.
+    let stats: Stats = serde_json::from_str(r#"{"shard_count": 5, "shards": [123]}"#).unwrap();
+
+    assert_eq!(
+      PostSchema::TopGg.serialize(&stats),
+      br#"{"shard_count":5,"shards":[123]}"#
+    );
+  }
+
+  #[test]
+  fn empty_shards_are_omitted() {
+    let stats = Stats::from_shards(Vec::<usize>::new(), None);
+    assert_eq!(
+      PostSchema::TopGg.serialize(&stats),
+      br#"{"server_count":0}"#
+    );
+
+    let stats: Stats = serde_json::from_str(r#"{"server_count": 42, "shards": []}"#).unwrap();
+    assert_eq!(
+      PostSchema::TopGg.serialize(&stats),
+      br#"{"server_count":42}"#
+    );
+
+    let custom = PostSchema::Custom {
+      server_count: "guildCount",
+      shard_count: "shardCount",
+      shards: "shards",
+      shard_id: "shardId",
+    };
+    assert_eq!(custom.serialize(&stats), br#"{"guildCount":42}"#);
+  }
+
+  #[test]
+  fn stats_round_trip() {
+    for stats in [
+      Stats::from(42),
+      Stats::from_count(42, Some(2)),
+      Stats::from_shards([20usize, 22], Some(1)),
+    ] {
+      let persisted = serde_json::to_string(&stats).unwrap();
+      let restored: Stats = serde_json::from_str(&persisted).unwrap();
+
+      assert_eq!(serde_json::to_string(&restored).unwrap(), persisted);
+      assert_eq!(restored.server_count(), stats.server_count());
+      assert_eq!(restored.shard_count(), stats.shard_count());
+      assert_eq!(restored.shards(), stats.shards());
+    }
+
+    assert_eq!(
+      serde_json::to_string(&Stats::from_shards([20usize, 22], Some(1))).unwrap(),
+      r#"{"shard_count":2,"server_count":42,"shards":[20,22],"shard_id":1}"#
+    );
+  }
+
+  #[test]
+  fn only_counts_are_posted() {
+    // there is nothing else to post, so no field is silently dropped.
+    let stats = Stats::from_shards([20usize, 22], Some(1));
+
+    let posted: serde_json::Value =
+      serde_json::from_slice(&PostSchema::TopGg.serialize(&stats)).unwrap();
+    let mut keys: Vec<_> = posted.as_object().unwrap().keys().cloned().collect();
+    keys.sort_unstable();
+
+    assert_eq!(keys, ["server_count", "shard_count", "shard_id", "shards"]);
+  }
+}
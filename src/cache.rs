@@ -0,0 +1,77 @@
+use core::time::Duration;
+use std::{collections::HashMap, sync::Mutex, time::Instant};
+
+/// An async trait for caching raw API responses, e.g. in [moka](https://docs.rs/moka) or Redis so that several processes share them. (See [`ClientBuilder::bot_cache`][crate::ClientBuilder::bot_cache])
+///
+/// It's described as follows (without [`async_trait`]'s macro expansion):
+/// ```rust,no_run
+/// # use core::time::Duration;
+/// #[async_trait::async_trait]
+/// pub trait Cache: Send + Sync + 'static {
+///   async fn get(&self, key: &str) -> Option<Vec<u8>>;
+///
+///   async fn set(&self, key: &str, value: Vec<u8>, ttl: Duration);
+/// }
+/// ```
+#[async_trait::async_trait]
+pub trait Cache: Send + Sync + 'static {
+  /// Retrieves the value stored under `key`, or [`None`] if there is none or it expired.
+  async fn get(&self, key: &str) -> Option<Vec<u8>>;
+
+  /// Stores `value` under `key`, replacing any previous one. It should expire after `ttl`.
+  async fn set(&self, key: &str, value: Vec<u8>, ttl: Duration);
+}
+
+/// A [`Cache`] that keeps its values in memory, for a single process.
+#[derive(Debug, Default)]
+pub struct MemoryCache {
+  // the value stored under each key, and when it expires.
+  entries: Mutex<HashMap<String, (Instant, Vec<u8>)>>,
+}
+
+impl MemoryCache {
+  /// Creates an empty [`MemoryCache`].
+  #[inline(always)]
+  pub fn new() -> Self {
+    Self::default()
+  }
+}
+
+#[async_trait::async_trait]
+impl Cache for MemoryCache {
+  async fn get(&self, key: &str) -> Option<Vec<u8>> {
+    self
+      .entries
+      .lock()
+      .unwrap()
+      .get(key)
+      .filter(|(expires_at, _)| Instant::now() < *expires_at)
+      .map(|(_, value)| value.clone())
+  }
+
+  async fn set(&self, key: &str, value: Vec<u8>, ttl: Duration) {
+    let now = Instant::now();
+    let mut entries = self.entries.lock().unwrap();
+
+    // forget expired values so that the map doesn't grow indefinitely.
+    entries.retain(|_, (expires_at, _)| now < *expires_at);
+    entries.insert(key.to_owned(), (now + ttl, value));
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[tokio::test]
+  async fn memory_caches_expire() {
+    let cache = MemoryCache::new();
+
+    cache.set("a", b"1".to_vec(), Duration::from_secs(60)).await;
+    cache.set("b", b"2".to_vec(), Duration::ZERO).await;
+
+    assert_eq!(cache.get("a").await.as_deref(), Some(&b"1"[..]));
+    assert_eq!(cache.get("b").await, None);
+    assert_eq!(cache.get("c").await, None);
+  }
+}
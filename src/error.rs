@@ -2,7 +2,10 @@ use core::{fmt, result};
 use std::error;
 
 /// A struct representing an error coming from this SDK - unexpected or not.
+///
+/// New variants may be added in minor releases, so matches on it need a wildcard arm.
 #[derive(Debug)]
+#[non_exhaustive]
 pub enum Error {
   /// An unexpected internal error coming from the client itself, preventing it from sending a request to [Top.gg](https://top.gg).
   InternalClientError(reqwest::Error),
@@ -10,14 +13,71 @@ pub enum Error {
   /// An unexpected error coming from [Top.gg](https://top.gg)'s servers themselves.
   InternalServerError,
 
+  /// The token is valid, but isn't allowed to perform this action, e.g. posting the statistics of a bot you don't own or fetching a private user profile. (403)
+  Forbidden,
+
   /// The requested resource does not exist. (404)
   NotFound,
 
+  /// [Top.gg](https://top.gg) rejected the request and explained why, e.g. as the statistics are invalid. (4xx)
+  Api {
+    /// The HTTP status code of the response.
+    status: u16,
+
+    /// The machine-readable error code sent by [Top.gg](https://top.gg).
+    code: String,
+
+    /// The human-readable explanation sent by [Top.gg](https://top.gg), if any.
+    message: Option<String>,
+  },
+
   /// The client is being ratelimited from sending more HTTP requests.
   Ratelimit {
     /// The amount of seconds before the ratelimit is lifted.
     retry_after: u16,
   },
+
+  /// The given [Top.gg API](https://docs.top.gg) token is malformed. (See [`Client::try_from`][crate::Client::try_from])
+  InvalidToken,
+
+  /// The given Discord ID is not a valid snowflake, e.g. as it's not numeric. (See [`Client::get_bot_str`][crate::Client::get_bot_str])
+  InvalidId {
+    /// The rejected ID.
+    id: String,
+  },
+
+  /// The given widget color is not a 6-digit hex color, optionally prefixed with `#`. (See [`WidgetOptionsBuilder::build`][crate::widget::WidgetOptionsBuilder::build])
+  InvalidColor {
+    /// The rejected color.
+    color: String,
+  },
+
+  /// The environment variable expected to contain the [Top.gg API](https://docs.top.gg) token is not set. (See [`Client::from_env`][crate::Client::from_env])
+  MissingToken {
+    /// The name of the environment variable.
+    var: String,
+  },
+
+  /// A [Top.gg](https://top.gg) response contains fields unknown to this library, e.g. after a schema change. (See [`ClientBuilder::strict`][crate::ClientBuilder::strict])
+  UnknownFields {
+    /// The path of each unknown field, e.g. `results.0.someNewField`.
+    fields: Vec<String>,
+  },
+
+  /// The statistics were not posted as their server count is zero, which usually indicates a bug. (See [`ClientBuilder::reject_zero_server_count`][crate::ClientBuilder::reject_zero_server_count])
+  SuspiciousStats,
+
+  /// The request didn't complete in time. (See [`Autoposter::with_shutdown_timeout`][crate::Autoposter::with_shutdown_timeout])
+  Timeout,
+
+  /// Posting the statistics of a specific shard failed. (See [`Client::post_shard_stats`][crate::Client::post_shard_stats])
+  Shard {
+    /// The ID of the shard whose statistics failed to post.
+    shard_id: usize,
+
+    /// The error that occurred while posting them.
+    error: Box<Error>,
+  },
 }
 
 impl fmt::Display for Error {
@@ -25,25 +85,144 @@ impl fmt::Display for Error {
     match self {
       Self::InternalClientError(err) => write!(f, "internal client error: {err}"),
       Self::InternalServerError => write!(f, "internal server error"),
+      Self::Forbidden => write!(f, "forbidden"),
       Self::NotFound => write!(f, "not found"),
+      Self::Api {
+        status,
+        code,
+        message,
+      } => match message {
+        Some(message) => write!(f, "Top.gg API error {code} ({status}): {message}"),
+        None => write!(f, "Top.gg API error {code} ({status})"),
+      },
       Self::Ratelimit { retry_after } => write!(
         f,
         "this client is ratelimited, try again in {} seconds",
         retry_after / 60
       ),
+      Self::InvalidToken => write!(f, "invalid Top.gg API token"),
+      Self::InvalidId { id } => write!(f, "invalid Discord ID: {id:?}"),
+      Self::InvalidColor { color } => write!(f, "invalid hex color: {color:?}"),
+      Self::MissingToken { var } => write!(f, "the {var} environment variable is not set"),
+      Self::UnknownFields { fields } => {
+        write!(f, "unknown fields in the response: {}", fields.join(", "))
+      }
+      Self::SuspiciousStats => write!(f, "refused to post a zero server count"),
+      Self::Timeout => write!(f, "timed out"),
+      Self::Shard { shard_id, error } => {
+        write!(f, "failed to post the stats of shard {shard_id}: {error}")
+      }
     }
   }
 }
 
+impl Error {
+  // a copy of this error for sharing it with several callers, unless it wraps a transport error that can't be copied.
+  pub(crate) fn try_clone(&self) -> Option<Self> {
+    Some(match self {
+      Self::InternalClientError(_) => return None,
+      Self::InternalServerError => Self::InternalServerError,
+      Self::Forbidden => Self::Forbidden,
+      Self::NotFound => Self::NotFound,
+      Self::Api {
+        status,
+        code,
+        message,
+      } => Self::Api {
+        status: *status,
+        code: code.clone(),
+        message: message.clone(),
+      },
+      Self::Ratelimit { retry_after } => Self::Ratelimit {
+        retry_after: *retry_after,
+      },
+      Self::InvalidToken => Self::InvalidToken,
+      Self::InvalidId { id } => Self::InvalidId { id: id.clone() },
+      Self::InvalidColor { color } => Self::InvalidColor {
+        color: color.clone(),
+      },
+      Self::MissingToken { var } => Self::MissingToken { var: var.clone() },
+      Self::UnknownFields { fields } => Self::UnknownFields {
+        fields: fields.clone(),
+      },
+      Self::SuspiciousStats => Self::SuspiciousStats,
+      Self::Timeout => Self::Timeout,
+      Self::Shard { shard_id, error } => Self::Shard {
+        shard_id: *shard_id,
+        error: Box::new(error.try_clone()?),
+      },
+    })
+  }
+}
+
 impl error::Error for Error {
   #[inline(always)]
   fn source(&self) -> Option<&(dyn error::Error + 'static)> {
     match self {
       Self::InternalClientError(err) => err.source(),
+      Self::Shard { error, .. } => Some(error.as_ref()),
       _ => None,
     }
   }
 }
 
+impl From<reqwest::Error> for Error {
+  /// Maps errors reading or decoding a response to [`InternalServerError`][Error::InternalServerError], and every other transport error to [`InternalClientError`][Error::InternalClientError].
+  fn from(err: reqwest::Error) -> Self {
+    if err.is_body() || err.is_decode() {
+      Self::InternalServerError
+    } else {
+      Self::InternalClientError(err)
+    }
+  }
+}
+
+impl From<serde_json::Error> for Error {
+  /// Maps any JSON error to [`InternalServerError`][Error::InternalServerError], as they come from unexpected responses sent by [Top.gg](https://top.gg)'s servers.
+  #[inline(always)]
+  fn from(_: serde_json::Error) -> Self {
+    Self::InternalServerError
+  }
+}
+
 /// The [`Result`][std::result::Result] type primarily used in this SDK.
 pub type Result<T> = result::Result<T, Error>;
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use std::{
+    io::{Read, Write},
+    net::TcpListener,
+    thread,
+  };
+
+  #[test]
+  fn json_errors_are_server_errors() {
+    let err = serde_json::from_str::<u64>("not json").unwrap_err();
+
+    assert!(matches!(Error::from(err), Error::InternalServerError));
+  }
+
+  #[tokio::test]
+  async fn transport_and_body_errors_are_told_apart() {
+    // nothing listens on port 1.
+    let err = reqwest::get("http://127.0.0.1:1").await.unwrap_err();
+
+    assert!(matches!(Error::from(err), Error::InternalClientError(_)));
+
+    // a server hanging up before sending the whole body.
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+
+    thread::spawn(move || {
+      let (mut stream, _) = listener.accept().unwrap();
+      let _ = stream.read(&mut [0; 1024]);
+      let _ = stream.write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 10\r\n\r\n{}");
+    });
+
+    let err = reqwest::get(url).await.unwrap().bytes().await.unwrap_err();
+
+    assert!(matches!(Error::from(err), Error::InternalServerError));
+  }
+}
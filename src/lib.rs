@@ -1,15 +1,33 @@
 #![doc = include_str!("../README.md")]
 #![cfg_attr(docsrs, feature(doc_cfg))]
 
+#[cfg(any(feature = "api", feature = "webhook"))]
 mod snowflake;
 
+/// Re-exports of the commonly used types of this library, enabled features permitting.
+///
+/// ```rust,no_run
+/// use topgg::prelude::*;
+/// ```
+pub mod prelude;
+
 cfg_if::cfg_if! {
   if #[cfg(feature = "api")] {
+    mod cache;
     mod client;
     mod error;
+    mod retry;
+    mod strict;
     mod util;
+    mod vote_gate;
+
+    #[cfg(test)]
+    mod mock;
+
+    #[cfg(feature = "tower")]
+    mod service;
 
-    #[cfg(feature = "autoposter")]
+    #[cfg(any(feature = "autoposter", feature = "tower"))]
     pub(crate) use client::InnerClient;
 
     /// Bot-related traits and structs.
@@ -18,11 +36,21 @@ cfg_if::cfg_if! {
     /// User-related structs.
     pub mod user;
 
+    /// Widget-related URL builders.
+    pub mod widget;
+
     #[doc(inline)]
-    pub use bot::Stats;
-    pub use client::Client;
+    pub use cache::{Cache, MemoryCache};
+    pub use bot::{BotsQuery, PostSchema, Reviews, ServerCount, ShardStat, Social, Stats};
+    pub use client::{Client, ClientBuilder, RateLimitStatus};
     pub use error::{Error, Result};
-    pub use snowflake::Snowflake; // for doc purposes
+    pub use retry::RetryPolicy;
+    pub use vote_gate::VoteGate;
+
+    #[cfg(feature = "tower")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "tower")))]
+    pub use service::ClientService;
+    pub use snowflake::{BotId, Snowflake, UserId};
   }
 }
 
@@ -0,0 +1,221 @@
+use crate::Client;
+use std::{
+  io::{BufRead, BufReader, Read, Write},
+  net::{TcpListener, TcpStream},
+  sync::{Arc, Mutex},
+  thread,
+};
+
+/// A request received by a [`MockServer`].
+#[derive(Clone, Debug)]
+pub(crate) struct Request {
+  pub(crate) method: String,
+  pub(crate) path: String,
+  pub(crate) headers: Vec<(String, String)>,
+  pub(crate) body: Vec<u8>,
+}
+
+impl Request {
+  pub(crate) fn header(&self, name: &str) -> Option<&str> {
+    self
+      .headers
+      .iter()
+      .find(|(key, _)| key.eq_ignore_ascii_case(name))
+      .map(|(_, value)| value.as_str())
+  }
+}
+
+/// A canned response sent back by a [`MockServer`].
+pub(crate) struct Response {
+  status: u16,
+  headers: Vec<(String, String)>,
+  body: String,
+}
+
+impl Response {
+  pub(crate) fn new(status: u16) -> Self {
+    Self {
+      status,
+      headers: Vec::new(),
+      body: String::new(),
+    }
+  }
+
+  pub(crate) fn json(body: impl Into<String>) -> Self {
+    Self::new(200).body(body)
+  }
+
+  pub(crate) fn body(mut self, body: impl Into<String>) -> Self {
+    self.body = body.into();
+    self.header("Content-Type", "application/json")
+  }
+
+  pub(crate) fn header(mut self, name: &str, value: &str) -> Self {
+    self.headers.push((name.to_owned(), value.to_owned()));
+    self
+  }
+}
+
+type Responder = dyn Fn(&Request) -> Response + Send + Sync;
+
+/// A minimal HTTP/1.1 server on a background thread that answers every request with a [`Response`] computed from it.
+pub(crate) struct MockServer {
+  url: String,
+  requests: Arc<Mutex<Vec<Request>>>,
+}
+
+impl MockServer {
+  pub(crate) fn start<F>(respond: F) -> Self
+  where
+    F: Fn(&Request) -> Response + Send + Sync + 'static,
+  {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let requests = Arc::new(Mutex::new(Vec::new()));
+    let respond: Arc<Responder> = Arc::new(respond);
+
+    {
+      let requests = Arc::clone(&requests);
+
+      thread::spawn(move || {
+        for stream in listener.incoming().flatten() {
+          let requests = Arc::clone(&requests);
+          let respond = Arc::clone(&respond);
+
+          thread::spawn(move || serve(stream, &requests, &*respond));
+        }
+      });
+    }
+
+    Self { url, requests }
+  }
+
+  pub(crate) fn url(&self) -> &str {
+    &self.url
+  }
+
+  /// Creates a [`Client`] that sends its requests to this server.
+  pub(crate) fn client(&self) -> Client {
+    Client::builder(String::from("token"))
+      .base_url(&self.url)
+      .build()
+      .unwrap()
+  }
+
+  pub(crate) fn requests(&self) -> Vec<Request> {
+    self.requests.lock().unwrap().clone()
+  }
+}
+
+fn serve(stream: TcpStream, requests: &Mutex<Vec<Request>>, respond: &Responder) {
+  let mut reader = BufReader::new(stream);
+  let mut line = String::new();
+
+  if reader.read_line(&mut line).is_err() {
+    return;
+  }
+
+  let mut parts = line.split_whitespace();
+  let method = parts.next().unwrap_or_default().to_owned();
+  let path = parts.next().unwrap_or_default().to_owned();
+  let mut headers = Vec::new();
+
+  loop {
+    line.clear();
+
+    if reader.read_line(&mut line).is_err() || line.trim().is_empty() {
+      break;
+    }
+
+    if let Some((key, value)) = line.split_once(':') {
+      headers.push((key.trim().to_owned(), value.trim().to_owned()));
+    }
+  }
+
+  let mut request = Request {
+    method,
+    path,
+    headers,
+    body: Vec::new(),
+  };
+
+  let length = request
+    .header("Content-Length")
+    .and_then(|length| length.parse().ok())
+    .unwrap_or(0);
+  request.body.resize(length, 0);
+
+  if reader.read_exact(&mut request.body).is_err() {
+    return;
+  }
+
+  requests.lock().unwrap().push(request.clone());
+
+  let response = respond(&request);
+
+  let mut output = format!(
+    "HTTP/1.1 {} MOCK\r\nContent-Length: {}\r\nConnection: close\r\n",
+    response.status,
+    response.body.len()
+  );
+
+  for (key, value) in &response.headers {
+    output.push_str(&format!("{key}: {value}\r\n"));
+  }
+
+  output.push_str("\r\n");
+  output.push_str(&response.body);
+
+  let mut stream = reader.into_inner();
+  let _ = stream.write_all(output.as_bytes());
+}
+
+/// A complete `GET /bots/:id` response body as sent by [Top.gg](https://top.gg), including the fields that [`Bot`][crate::Bot] doesn't expose.
+pub(crate) const REAL_BOT_JSON: &str = r#"{
+  "defAvatar": "6debd47ed13483642cf09e832ed0bc1b",
+  "invite": "",
+  "website": "https://discordbots.org",
+  "support": "KYZsaFb",
+  "github": "https://github.com/DiscordBotList/Luca",
+  "longdesc": "Luca only works in the **Discord Bot List** server.",
+  "shortdesc": "Luca is a bot for managing and informing members of the server",
+  "prefix": "- or @Luca#1375",
+  "lib": "discord.js",
+  "clientid": "264811613708746752",
+  "avatar": "7edcc4c6fbb0b23762455ca139f0e1c9",
+  "id": "264811613708746752",
+  "discriminator": "1375",
+  "username": "Luca",
+  "date": "2017-04-26T18:08:17.125Z",
+  "server_count": 2,
+  "shard_count": 1,
+  "guilds": ["417723229721853963", "264445053596991498"],
+  "shards": [],
+  "monthlyPoints": 19,
+  "points": 397,
+  "certifiedBot": false,
+  "owners": ["129908908096487424"],
+  "tags": ["Fun", "Social", "Moderation"],
+  "bannerUrl": "https://example.com/banner.png",
+  "reviews": {"averageScore": 4.5, "count": 12},
+  "vanity": "luca",
+  "donatebotguildid": ""
+}"#;
+
+/// A `GET /bots/:id` response body, with `extra` spliced in as additional (comma-prefixed) fields.
+pub(crate) fn bot_json(extra: &str) -> String {
+  format!(
+    r#"{{
+      "id": "264811613708746752",
+      "username": "Luca",
+      "discriminator": "1375",
+      "prefix": "- or @Luca",
+      "shortdesc": "Luca is a bot for managing and informing members of the server",
+      "owners": ["129908908096487424"],
+      "date": "2017-04-26T18:08:17.125Z",
+      "certifiedBot": false,
+      "points": 397,
+      "monthlyPoints": 19{extra}
+    }}"#
+  )
+}
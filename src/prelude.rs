@@ -0,0 +1,29 @@
+cfg_if::cfg_if! {
+  if #[cfg(feature = "api")] {
+    pub use crate::{BotId, Client, ClientBuilder, Error, ShardStat, Stats, UserId};
+  }
+}
+
+cfg_if::cfg_if! {
+  if #[cfg(feature = "autoposter")] {
+    pub use crate::autoposter::{AsClient, Autoposter, Handler, SharedStats};
+
+    #[cfg(feature = "serenity")]
+    pub use crate::autoposter::Serenity;
+
+    #[cfg(feature = "twilight")]
+    pub use crate::autoposter::Twilight;
+  }
+}
+
+cfg_if::cfg_if! {
+  if #[cfg(feature = "webhook")] {
+    pub use crate::{Vote, VoteDedup, VoteEvent};
+
+    #[cfg(any(feature = "actix-web", feature = "rocket"))]
+    pub use crate::IncomingVote;
+
+    #[cfg(any(feature = "axum", feature = "warp"))]
+    pub use crate::VoteHandler;
+  }
+}
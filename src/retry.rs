@@ -0,0 +1,172 @@
+use core::time::Duration;
+use std::{
+  collections::hash_map::RandomState,
+  hash::{BuildHasher, Hasher},
+};
+
+/// A schedule for retrying requests that failed transiently, i.e. that couldn't be sent or that [Top.gg](https://top.gg)'s servers failed to answer (5xx). (See [`ClientBuilder::retry_policy`][crate::ClientBuilder::retry_policy])
+///
+/// The delay before each retry doubles from `base` up to `max_delay`. With jitter enabled, each delay is then randomly shortened by up to half, so that many clients failing at once don't retry in lockstep.
+///
+/// Ratelimits are never retried here, as [Top.gg](https://top.gg) tells how long to wait instead. The [`Autoposter`][crate::Autoposter] posts through its client, so configuring this once covers both.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```rust,no_run
+/// use core::time::Duration;
+/// use topgg::{Client, RetryPolicy};
+///
+/// let _client = Client::builder(std::env::var("TOPGG_TOKEN").unwrap())
+///   .retry_policy(
+///     RetryPolicy::new()
+///       .max_attempts(3)
+///       .base(Duration::from_millis(500)),
+///   )
+///   .build()
+///   .unwrap();
+/// ```
+#[must_use]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RetryPolicy {
+  max_attempts: u32,
+  base: Duration,
+  max_delay: Duration,
+  jitter: bool,
+}
+
+impl Default for RetryPolicy {
+  #[inline(always)]
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl RetryPolicy {
+  /// Creates a [`RetryPolicy`] that never retries, which is the default. It starts at a `base` of 1 second, a `max_delay` of 30 seconds and has jitter enabled.
+  #[inline(always)]
+  pub const fn new() -> Self {
+    Self {
+      max_attempts: 1,
+      base: Duration::from_secs(1),
+      max_delay: Duration::from_secs(30),
+      jitter: true,
+    }
+  }
+
+  /// Sets the maximum amount of attempts per request, including the first one. `1` disables retries.
+  ///
+  /// # Panics
+  ///
+  /// Panics if the maximum is zero.
+  #[inline(always)]
+  pub const fn max_attempts(mut self, max_attempts: u32) -> Self {
+    assert!(max_attempts != 0, "The maximum attempts mustn't be zero.");
+
+    self.max_attempts = max_attempts;
+    self
+  }
+
+  /// Sets the delay before the first retry.
+  #[inline(always)]
+  pub const fn base(mut self, base: Duration) -> Self {
+    self.base = base;
+    self
+  }
+
+  /// Sets the longest delay between two attempts.
+  #[inline(always)]
+  pub const fn max_delay(mut self, max_delay: Duration) -> Self {
+    self.max_delay = max_delay;
+    self
+  }
+
+  /// Whether to randomly shorten each delay by up to half.
+  #[inline(always)]
+  pub const fn jitter(mut self, jitter: bool) -> Self {
+    self.jitter = jitter;
+    self
+  }
+
+  /// The delay to wait after failed attempt number `attempt` (starting at 1) before retrying, or [`None`] if no attempts are left.
+  #[must_use]
+  pub fn delay(&self, attempt: u32) -> Option<Duration> {
+    let factor = if self.jitter {
+      // the standard library seeds every hasher randomly, which is plenty for spreading retries out.
+      RandomState::new().build_hasher().finish() as f64 / u64::MAX as f64
+    } else {
+      0.0
+    };
+
+    self.delay_with(attempt, factor)
+  }
+
+  // the delay with `factor` (from 0 to 1) as the source of jitter.
+  fn delay_with(&self, attempt: u32, factor: f64) -> Option<Duration> {
+    if attempt == 0 || attempt >= self.max_attempts {
+      return None;
+    }
+
+    let delay = self
+      .base
+      .saturating_mul(1 << (attempt - 1).min(31))
+      .min(self.max_delay);
+
+    Some(if self.jitter {
+      delay.mul_f64(1.0 - factor / 2.0)
+    } else {
+      delay
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn delays(policy: RetryPolicy, factor: f64) -> Vec<Duration> {
+    (1..)
+      .map_while(|attempt| policy.delay_with(attempt, factor))
+      .collect()
+  }
+
+  #[test]
+  fn delays_double_up_to_the_max() {
+    let policy = RetryPolicy::new()
+      .max_attempts(6)
+      .base(Duration::from_secs(1))
+      .max_delay(Duration::from_secs(5))
+      .jitter(false);
+
+    assert_eq!(
+      delays(policy, 0.5),
+      [1, 2, 4, 5, 5].map(Duration::from_secs)
+    );
+    assert_eq!(policy.delay(1), Some(Duration::from_secs(1)));
+    assert!(delays(RetryPolicy::new(), 0.0).is_empty());
+  }
+
+  #[test]
+  fn jitter_shortens_delays_by_up_to_half() {
+    let policy = RetryPolicy::new()
+      .max_attempts(4)
+      .base(Duration::from_secs(2))
+      .max_delay(Duration::from_secs(6));
+
+    assert_eq!(delays(policy, 0.0), [2, 4, 6].map(Duration::from_secs));
+    assert_eq!(delays(policy, 1.0), [1, 2, 3].map(Duration::from_secs));
+    assert_eq!(
+      delays(policy, 0.5),
+      [1500, 3000, 4500].map(Duration::from_millis)
+    );
+
+    for attempt in 1..4 {
+      let delay = policy.delay(attempt).unwrap();
+      let max = policy.jitter(false).delay(attempt).unwrap();
+
+      assert!(delay <= max && delay >= max / 2);
+    }
+    assert_eq!(policy.delay(4), None);
+  }
+}
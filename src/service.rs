@@ -0,0 +1,124 @@
+use crate::{client::ApiVersion, Error, InnerClient, Result};
+use reqwest::{header::HeaderMap, Method, Response};
+use std::{
+  future::Future,
+  pin::Pin,
+  sync::Arc,
+  task::{Context, Poll},
+};
+use tower_service::Service;
+
+/// A [`tower::Service`](https://docs.rs/tower/latest/tower/trait.Service.html) sending raw requests to the [Top.gg API](https://docs.top.gg), letting you wrap it in your own middleware like timeouts, retries or tracing. (See [`Client::service`][crate::Client::service])
+///
+/// It takes a method and a path, just like [`Client::request_raw`][crate::Client::request_raw], and likewise returns the response as-is without looking at its status.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```rust,no_run
+/// use reqwest::Method;
+/// use std::time::Duration;
+/// use topgg::Client;
+/// use tower::{ServiceBuilder, ServiceExt};
+///
+/// # async fn run() -> Result<(), tower::BoxError> {
+/// let client = Client::new(std::env::var("TOPGG_TOKEN").unwrap());
+///
+/// let service = ServiceBuilder::new()
+///   .timeout(Duration::from_secs(10))
+///   .service(client.service());
+///
+/// let response = service
+///   .oneshot((Method::GET, String::from("/weekend")))
+///   .await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct ClientService {
+  inner: Arc<InnerClient>,
+}
+
+impl ClientService {
+  #[inline(always)]
+  pub(crate) fn new(inner: Arc<InnerClient>) -> Self {
+    Self { inner }
+  }
+}
+
+impl Service<(Method, String)> for ClientService {
+  type Response = Response;
+  type Error = Error;
+  type Future = Pin<Box<dyn Future<Output = Result<Response>> + Send>>;
+
+  #[inline(always)]
+  fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<()>> {
+    Poll::Ready(Ok(()))
+  }
+
+  fn call(&mut self, (method, path): (Method, String)) -> Self::Future {
+    let inner = Arc::clone(&self.inner);
+
+    Box::pin(async move {
+      inner
+        .execute_tracked(method, &path, Vec::new(), ApiVersion::V0, HeaderMap::new())
+        .await
+        .map_err(Error::InternalClientError)
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::mock::{MockServer, Response};
+  use reqwest::{Method, StatusCode};
+  use std::time::Duration;
+  use tower::{ServiceBuilder, ServiceExt};
+
+  #[tokio::test]
+  async fn composes_with_tower_layers() {
+    let server = MockServer::start(|_| Response::json(r#"{"is_weekend":true}"#));
+    let client = server.client();
+
+    let service = ServiceBuilder::new()
+      .timeout(Duration::from_secs(10))
+      .map_response(|response: reqwest::Response| response.status())
+      .service(client.service());
+
+    let status = service
+      .oneshot((Method::GET, String::from("/weekend")))
+      .await
+      .unwrap();
+
+    assert_eq!(status, StatusCode::OK);
+
+    let requests = server.requests();
+    assert_eq!(requests[0].path, "/weekend");
+    assert_eq!(requests[0].header("Authorization"), Some("token"));
+  }
+
+  #[tokio::test]
+  async fn records_ratelimits() {
+    let server = MockServer::start(|_| {
+      Response::new(200)
+        .header("X-RateLimit-Limit", "2")
+        .header("X-RateLimit-Remaining", "1")
+    });
+    let client = server.client();
+
+    let response = client
+      .service()
+      .oneshot((Method::POST, String::from("/bots/stats")))
+      .await
+      .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    assert_eq!(
+      client
+        .post_stats_budget()
+        .map(|status| (status.limit, status.remaining)),
+      Some((2, 1))
+    );
+  }
+}
@@ -8,46 +8,65 @@ where
   String::deserialize(deserializer).and_then(|s| s.parse().map_err(D::Error::custom))
 }
 
+// an ID sent either as a string or as a number.
+#[cfg(feature = "api")]
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum AnyId {
+  String(String),
+  Number(u64),
+  Other(serde::de::IgnoredAny),
+}
+
+// IDs that can't be parsed are skipped.
 #[inline(always)]
 #[cfg(feature = "api")]
 pub(crate) fn deserialize_vec<'de, D>(deserializer: D) -> Result<Vec<u64>, D::Error>
 where
   D: Deserializer<'de>,
 {
-  Deserialize::deserialize(deserializer)
-    .map(|s: Vec<String>| s.into_iter().filter_map(|next| next.parse().ok()).collect())
+  Deserialize::deserialize(deserializer).map(|ids: Vec<AnyId>| {
+    ids
+      .into_iter()
+      .filter_map(|id| match id {
+        AnyId::String(id) => id.trim().parse().ok(),
+        AnyId::Number(id) => Some(id),
+        AnyId::Other(_) => None,
+      })
+      .collect()
+  })
 }
 
-/// A trait that represents any datatype that can be interpreted as a Discord snowflake/ID.
-pub trait Snowflake {
-  /// The method that converts this value to a [`u64`].
-  fn as_snowflake(&self) -> u64;
-}
+cfg_if::cfg_if! {
+  if #[cfg(feature = "api")] {
+    /// A trait that represents any datatype that can be interpreted as a Discord snowflake/ID.
+    pub trait Snowflake {
+      /// The method that converts this value to a [`u64`].
+      fn as_snowflake(&self) -> u64;
+    }
 
-macro_rules! impl_snowflake(
-  ($(#[$attr:meta] )?$self:ident,$t:ty,$body:expr) => {
-    $(#[$attr])?
-    impl Snowflake for $t {
-      #[inline(always)]
-      fn as_snowflake(&$self) -> u64 {
-        $body
+    macro_rules! impl_snowflake(
+      ($(#[$attr:meta] )?$self:ident,$t:ty,$body:expr) => {
+        $(#[$attr])?
+        impl Snowflake for $t {
+          #[inline(always)]
+          fn as_snowflake(&$self) -> u64 {
+            $body
+          }
+        }
       }
-    }
-  }
-);
+    );
 
-impl_snowflake!(self, u64, *self);
+    impl_snowflake!(self, u64, *self);
 
-macro_rules! impl_string(
-  ($($t:ty),+) => {$(
-    impl_snowflake!(self, $t, (*self).parse().expect("invalid snowflake as it's not numeric"));
-  )+}
-);
+    macro_rules! impl_string(
+      ($($t:ty),+) => {$(
+        impl_snowflake!(self, $t, (*self).parse().expect("invalid snowflake as it's not numeric"));
+      )+}
+    );
 
-impl_string!(&str, String);
+    impl_string!(&str, String);
 
-cfg_if::cfg_if! {
-  if #[cfg(feature = "api")] {
     macro_rules! impl_topgg_idstruct(
       ($($t:ty),+) => {$(
         impl_snowflake!(self, &$t, (*self).id);
@@ -59,11 +78,50 @@ cfg_if::cfg_if! {
       crate::user::User,
       crate::user::Voter
     );
+
+    macro_rules! impl_id_newtype(
+      ($($(#[$attr:meta])* $t:ident),+) => {$(
+        $(#[$attr])*
+        #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        pub struct $t(pub u64);
+
+        impl From<u64> for $t {
+          #[inline(always)]
+          fn from(id: u64) -> Self {
+            Self(id)
+          }
+        }
+
+        impl From<$t> for u64 {
+          #[inline(always)]
+          fn from(id: $t) -> Self {
+            id.0
+          }
+        }
+
+        impl std::fmt::Display for $t {
+          #[inline(always)]
+          fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            self.0.fmt(f)
+          }
+        }
+
+        impl_snowflake!(self, $t, self.0);
+      )+}
+    );
+
+    impl_id_newtype!(
+      /// The Discord ID of a bot, for telling it apart from other IDs at compile time. It can be used anywhere a [`Snowflake`] is expected.
+      BotId,
+
+      /// The Discord ID of a user, for telling it apart from other IDs at compile time. It can be used anywhere a [`Snowflake`] is expected.
+      UserId
+    );
   }
 }
 
 cfg_if::cfg_if! {
-  if #[cfg(feature = "serenity")] {
+  if #[cfg(all(feature = "api", feature = "serenity"))] {
     impl_snowflake!(
       #[cfg_attr(docsrs, doc(cfg(feature = "serenity")))] self,
       &serenity::model::guild::Member,
@@ -102,7 +160,7 @@ cfg_if::cfg_if! {
 }
 
 cfg_if::cfg_if! {
-  if #[cfg(feature = "serenity-cached")] {
+  if #[cfg(all(feature = "api", feature = "serenity-cached"))] {
     use std::ops::Deref;
 
     macro_rules! impl_serenity_cacheref(
@@ -120,7 +178,7 @@ cfg_if::cfg_if! {
 }
 
 cfg_if::cfg_if! {
-  if #[cfg(feature = "twilight")] {
+  if #[cfg(all(feature = "api", feature = "twilight"))] {
     #[cfg_attr(docsrs, doc(cfg(feature = "twilight")))]
     impl<I> Snowflake for twilight_model::id::Id<I> {
       #[inline(always)]
@@ -150,7 +208,7 @@ cfg_if::cfg_if! {
 }
 
 cfg_if::cfg_if! {
-  if #[cfg(feature = "twilight-cached")] {
+  if #[cfg(all(feature = "api", feature = "twilight-cached"))] {
     impl_snowflake!(
       #[cfg_attr(docsrs, doc(cfg(feature = "twilight-cached")))] self,
       &twilight_cache_inmemory::model::CachedMember,
@@ -158,3 +216,25 @@ cfg_if::cfg_if! {
     );
   }
 }
+
+#[cfg(all(test, feature = "api"))]
+mod tests {
+  use super::*;
+  use std::collections::HashMap;
+
+  #[test]
+  fn id_newtypes() {
+    let bot_id = BotId::from(264811613708746752);
+
+    assert_eq!(u64::from(bot_id), 264811613708746752);
+    assert_eq!(bot_id.as_snowflake(), 264811613708746752);
+    assert_eq!(bot_id.to_string(), "264811613708746752");
+
+    let mut votes = HashMap::new();
+    votes.insert(UserId(1), 2);
+    *votes.entry(UserId::from(1)).or_insert(0) += 1;
+
+    assert_eq!(votes[&UserId(1)], 3);
+    assert_eq!(votes.len(), 1);
+  }
+}
@@ -0,0 +1,240 @@
+use core::fmt::Display;
+use serde::de::{
+  self, value::StrDeserializer, DeserializeOwned, DeserializeSeed, Deserializer, IntoDeserializer,
+  MapAccess, SeqAccess, Visitor,
+};
+use serde_json::{map, Value};
+use std::{cell::RefCell, iter::Enumerate, slice};
+
+/// Deserializes `value` like [`serde_json::from_value`] does, along with the path of every field that `T` doesn't know about, e.g. `results.0.someNewField`.
+pub(crate) fn from_value<T>(value: &Value) -> serde_json::Result<(T, Vec<String>)>
+where
+  T: DeserializeOwned,
+{
+  let unknown = RefCell::new(Vec::new());
+  let parsed = T::deserialize(Checked {
+    value,
+    path: String::new(),
+    unknown: &unknown,
+  })?;
+
+  Ok((parsed, unknown.into_inner()))
+}
+
+// a deserializer that compares every object deserialized as a struct against the fields of that struct.
+struct Checked<'a, 'u> {
+  value: &'a Value,
+  path: String,
+  unknown: &'u RefCell<Vec<String>>,
+}
+
+impl<'a, 'u> Checked<'a, 'u> {
+  fn path_to(&self, key: impl Display) -> String {
+    if self.path.is_empty() {
+      key.to_string()
+    } else {
+      format!("{}.{key}", self.path)
+    }
+  }
+
+  fn child(&self, value: &'a Value, key: impl Display) -> Self {
+    Self {
+      value,
+      path: self.path_to(key),
+      unknown: self.unknown,
+    }
+  }
+}
+
+impl<'de> Deserializer<'de> for Checked<'de, '_> {
+  type Error = serde_json::Error;
+
+  fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+  where
+    V: Visitor<'de>,
+  {
+    match self.value {
+      Value::Array(values) => visitor.visit_seq(Elements {
+        parent: &self,
+        values: values.iter().enumerate(),
+      }),
+      Value::Object(entries) => visitor.visit_map(Entries {
+        parent: &self,
+        entries: entries.iter(),
+        pending: None,
+      }),
+      value => value.deserialize_any(visitor),
+    }
+  }
+
+  fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+  where
+    V: Visitor<'de>,
+  {
+    match self.value {
+      Value::Null => visitor.visit_none(),
+      _ => visitor.visit_some(self),
+    }
+  }
+
+  fn deserialize_newtype_struct<V>(
+    self,
+    _name: &'static str,
+    visitor: V,
+  ) -> Result<V::Value, Self::Error>
+  where
+    V: Visitor<'de>,
+  {
+    visitor.visit_newtype_struct(self)
+  }
+
+  fn deserialize_struct<V>(
+    self,
+    _name: &'static str,
+    fields: &'static [&'static str],
+    visitor: V,
+  ) -> Result<V::Value, Self::Error>
+  where
+    V: Visitor<'de>,
+  {
+    if let Value::Object(entries) = self.value {
+      self.unknown.borrow_mut().extend(
+        entries
+          .keys()
+          .filter(|key| !fields.contains(&key.as_str()))
+          .map(|key| self.path_to(key)),
+      );
+    }
+
+    self.deserialize_any(visitor)
+  }
+
+  // enums are only ever sent as plain strings.
+  fn deserialize_enum<V>(
+    self,
+    name: &'static str,
+    variants: &'static [&'static str],
+    visitor: V,
+  ) -> Result<V::Value, Self::Error>
+  where
+    V: Visitor<'de>,
+  {
+    self.value.deserialize_enum(name, variants, visitor)
+  }
+
+  serde::forward_to_deserialize_any! {
+    bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+    bytes byte_buf unit unit_struct seq tuple tuple_struct map identifier ignored_any
+  }
+}
+
+struct Elements<'p, 'a, 'u> {
+  parent: &'p Checked<'a, 'u>,
+  values: Enumerate<slice::Iter<'a, Value>>,
+}
+
+impl<'de> SeqAccess<'de> for Elements<'_, 'de, '_> {
+  type Error = serde_json::Error;
+
+  fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>, Self::Error>
+  where
+    T: DeserializeSeed<'de>,
+  {
+    self
+      .values
+      .next()
+      .map(|(index, value)| seed.deserialize(self.parent.child(value, index)))
+      .transpose()
+  }
+
+  #[inline(always)]
+  fn size_hint(&self) -> Option<usize> {
+    Some(self.values.len())
+  }
+}
+
+struct Entries<'p, 'a, 'u> {
+  parent: &'p Checked<'a, 'u>,
+  entries: map::Iter<'a>,
+  pending: Option<(&'a String, &'a Value)>,
+}
+
+impl<'de> MapAccess<'de> for Entries<'_, 'de, '_> {
+  type Error = serde_json::Error;
+
+  fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, Self::Error>
+  where
+    K: DeserializeSeed<'de>,
+  {
+    self.pending = self.entries.next();
+
+    self
+      .pending
+      .map(|(key, _)| {
+        let key: StrDeserializer<'_, Self::Error> = key.as_str().into_deserializer();
+
+        seed.deserialize(key)
+      })
+      .transpose()
+  }
+
+  fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value, Self::Error>
+  where
+    V: DeserializeSeed<'de>,
+  {
+    let (key, value) = self
+      .pending
+      .take()
+      .ok_or_else(|| de::Error::custom("value requested before its key"))?;
+
+    seed.deserialize(self.parent.child(value, key))
+  }
+
+  #[inline(always)]
+  fn size_hint(&self) -> Option<usize> {
+    Some(self.entries.len())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use serde::Deserialize;
+
+  #[derive(Debug, Deserialize, PartialEq)]
+  struct Inner {
+    count: usize,
+  }
+
+  #[derive(Debug, Deserialize, PartialEq)]
+  struct Outer {
+    #[serde(rename = "innerList")]
+    inner: Vec<Inner>,
+    note: Option<String>,
+  }
+
+  #[test]
+  fn unknown_fields_are_reported_by_path() {
+    let value = serde_json::json!({
+      "innerList": [{"count": 1}, {"count": 2, "extra": true}],
+      "note": null,
+      "new": {"count": 3},
+    });
+
+    let (outer, unknown) = from_value::<Outer>(&value).unwrap();
+
+    assert_eq!(
+      outer,
+      Outer {
+        inner: vec![Inner { count: 1 }, Inner { count: 2 }],
+        note: None,
+      }
+    );
+    assert_eq!(unknown, ["new", "innerList.1.extra"]);
+  }
+
+  #[test]
+  fn type_errors_are_still_reported() {
+    assert!(from_value::<Outer>(&serde_json::json!({"innerList": [{"count": "x"}]})).is_err());
+  }
+}
@@ -1,6 +1,6 @@
 use crate::{snowflake, util};
 use chrono::{DateTime, Utc};
-use serde::Deserialize;
+use serde::{de::IgnoredAny, Deserialize};
 
 /// A struct representing a user's social links.
 #[derive(Clone, Debug, Deserialize)]
@@ -26,6 +26,40 @@ pub struct Socials {
   pub youtube: Option<String>,
 }
 
+bitflags::bitflags! {
+  /// The [Top.gg](https://top.gg) roles of a user, as a set of flags. (See [`User::flags`])
+  ///
+  /// # Examples
+  ///
+  /// Basic usage:
+  ///
+  /// ```rust,no_run
+  /// use topgg::user::UserFlags;
+  ///
+  /// let flags = UserFlags::SUPPORTER | UserFlags::CERTIFIED_DEV;
+  ///
+  /// assert!(flags.contains(UserFlags::SUPPORTER));
+  /// assert!(!flags.intersects(UserFlags::MODERATOR | UserFlags::ADMIN));
+  /// ```
+  #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+  pub struct UserFlags: u32 {
+    /// The user is a [Top.gg](https://top.gg) supporter.
+    const SUPPORTER = 1 << 0;
+
+    /// The user is a [Top.gg](https://top.gg) certified developer.
+    const CERTIFIED_DEV = 1 << 1;
+
+    /// The user is a [Top.gg](https://top.gg) moderator.
+    const MODERATOR = 1 << 2;
+
+    /// The user is a [Top.gg](https://top.gg) website moderator.
+    const WEB_MODERATOR = 1 << 3;
+
+    /// The user is a [Top.gg](https://top.gg) website administrator.
+    const ADMIN = 1 << 4;
+  }
+}
+
 util::debug_struct! {
   /// A struct representing a user logged into [Top.gg](https://top.gg).
   #[must_use]
@@ -48,7 +82,7 @@ util::debug_struct! {
       banner: Option<String>,
 
       /// A struct of this user's social links.
-      #[serde(rename = "social")]
+      #[serde(default, rename = "social")]
       socials: Option<Socials>,
 
       /// Whether this user is a [Top.gg](https://top.gg) supporter or not.
@@ -75,6 +109,16 @@ util::debug_struct! {
     private {
       #[serde(default, deserialize_with = "util::deserialize_optional_string")]
       avatar: Option<String>,
+
+      // sent by Top.gg, but either deprecated or redundant. listed so that strict clients accept them.
+      #[serde(default, rename = "discriminator")]
+      _discriminator: IgnoredAny,
+
+      #[serde(default, rename = "defAvatar")]
+      _default_avatar: IgnoredAny,
+
+      #[serde(default, rename = "color")]
+      _color: IgnoredAny,
     }
 
     getters(self) {
@@ -93,6 +137,29 @@ util::debug_struct! {
       avatar: String => {
         util::get_avatar(&self.avatar, self.id)
       }
+
+      /// Retrieves the Discord CDN URL of this user's own avatar, or [`None`] if they don't have one. Unlike [`avatar`][User::avatar], this doesn't fall back to Discord's default avatar.
+      ///
+      /// Its format will either be PNG or GIF if animated.
+      #[must_use]
+      #[inline(always)]
+      avatar_url: Option<String> => {
+        util::get_custom_avatar(&self.avatar, self.id)
+      }
+
+      /// This user's [Top.gg](https://top.gg) roles as a set of [`UserFlags`], derived from the `is_*` fields.
+      #[must_use]
+      flags: UserFlags => {
+        let mut flags = UserFlags::empty();
+
+        flags.set(UserFlags::SUPPORTER, self.is_supporter);
+        flags.set(UserFlags::CERTIFIED_DEV, self.is_certified_dev);
+        flags.set(UserFlags::MODERATOR, self.is_moderator);
+        flags.set(UserFlags::WEB_MODERATOR, self.is_web_moderator);
+        flags.set(UserFlags::ADMIN, self.is_admin);
+
+        flags
+      }
     }
   }
 }
@@ -124,6 +191,7 @@ util::debug_struct! {
     }
 
     private {
+      #[serde(default)]
       avatar: Option<String>,
     }
 
@@ -146,3 +214,59 @@ util::debug_struct! {
     }
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn user(avatar: &str) -> User {
+    serde_json::from_str(&format!(
+      r#"{{
+        "id": "140862798832861184",
+        "username": "Xetera",
+        "avatar": {avatar},
+        "supporter": false,
+        "certifiedDev": false,
+        "mod": false,
+        "webMod": false,
+        "admin": false
+      }}"#
+    ))
+    .unwrap()
+  }
+
+  #[test]
+  fn avatar_url() {
+    assert_eq!(
+      user(r#""3a3d8bd2e2e0d9c1a5f21b0d8e920dbb""#).avatar_url().as_deref(),
+      Some("https://cdn.discordapp.com/avatars/140862798832861184/3a3d8bd2e2e0d9c1a5f21b0d8e920dbb.png?size=1024")
+    );
+    assert_eq!(
+      user(r#""a_3a3d8bd2e2e0d9c1a5f21b0d8e920dbb""#).avatar_url().as_deref(),
+      Some("https://cdn.discordapp.com/avatars/140862798832861184/a_3a3d8bd2e2e0d9c1a5f21b0d8e920dbb.gif?size=1024")
+    );
+
+    let user = user("null");
+    assert_eq!(user.avatar_url(), None);
+    assert!(user
+      .avatar()
+      .starts_with("https://cdn.discordapp.com/embed/avatars/"));
+  }
+
+  #[test]
+  fn flags() {
+    let mut user = user("null");
+    assert!(user.flags().is_empty());
+
+    user.is_supporter = true;
+    user.is_admin = true;
+    assert_eq!(user.flags(), UserFlags::SUPPORTER | UserFlags::ADMIN);
+
+    let flags = UserFlags::from_bits_truncate(0b1_0110 | 1 << 31);
+    assert_eq!(
+      flags,
+      UserFlags::CERTIFIED_DEV | UserFlags::MODERATOR | UserFlags::ADMIN
+    );
+    assert_eq!(flags.bits(), 0b1_0110);
+  }
+}
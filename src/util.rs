@@ -1,7 +1,11 @@
-use crate::Error;
+use crate::{snowflake, strict};
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
 use chrono::{DateTime, TimeZone, Utc};
 use reqwest::Response;
-use serde::{de::DeserializeOwned, Deserialize, Deserializer};
+use serde::{
+  de::{self, DeserializeOwned},
+  Deserialize, Deserializer,
+};
 
 const DISCORD_EPOCH: u64 = 1_420_070_400_000;
 
@@ -104,6 +108,64 @@ where
   Option::deserialize(deserializer).map(|res| res.unwrap_or_default())
 }
 
+// a count sent either as a number or as a numeric string, as the API isn't consistent about it.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Count {
+  Number(usize),
+  String(String),
+}
+
+impl Count {
+  fn parse<E: de::Error>(self) -> Result<usize, E> {
+    match self {
+      Self::Number(count) => Ok(count),
+      Self::String(count) => count
+        .trim()
+        .parse()
+        .map_err(|_| E::custom(format!("invalid count: {count:?}"))),
+    }
+  }
+}
+
+#[inline(always)]
+pub(crate) fn deserialize_count<'de, D>(deserializer: D) -> Result<usize, D::Error>
+where
+  D: Deserializer<'de>,
+{
+  Count::deserialize(deserializer)?.parse()
+}
+
+#[inline(always)]
+pub(crate) fn deserialize_optional_count<'de, D>(deserializer: D) -> Result<Option<usize>, D::Error>
+where
+  D: Deserializer<'de>,
+{
+  Option::<Count>::deserialize(deserializer)?
+    .map(Count::parse)
+    .transpose()
+}
+
+#[inline(always)]
+pub(crate) fn deserialize_optional_counts<'de, D>(
+  deserializer: D,
+) -> Result<Option<Vec<usize>>, D::Error>
+where
+  D: Deserializer<'de>,
+{
+  Option::<Vec<Count>>::deserialize(deserializer)?
+    .map(|counts| counts.into_iter().map(Count::parse).collect())
+    .transpose()
+}
+
+#[inline(always)]
+pub(crate) fn deserialize_counts<'de, D>(deserializer: D) -> Result<Vec<usize>, D::Error>
+where
+  D: Deserializer<'de>,
+{
+  deserialize_optional_counts(deserializer).map(Option::unwrap_or_default)
+}
+
 #[inline(always)]
 pub(crate) fn get_creation_date(id: u64) -> DateTime<Utc> {
   Utc
@@ -112,30 +174,66 @@ pub(crate) fn get_creation_date(id: u64) -> DateTime<Utc> {
     .unwrap()
 }
 
+#[derive(Deserialize)]
+struct TokenPayload {
+  #[serde(deserialize_with = "snowflake::deserialize")]
+  id: u64,
+}
+
+/// Extracts the bot ID from a [Top.gg](https://top.gg) token's JWT payload. The token's signature is not verified.
+pub(crate) fn parse_token_id(token: &str) -> Option<u64> {
+  let payload = token.split('.').nth(1)?;
+  let bytes = URL_SAFE_NO_PAD.decode(payload.trim_end_matches('=')).ok()?;
+
+  serde_json::from_slice::<TokenPayload>(&bytes)
+    .ok()
+    .map(|payload| payload.id)
+}
+
 #[inline(always)]
 pub(crate) async fn parse_json<T>(response: Response) -> crate::Result<T>
 where
   T: DeserializeOwned,
 {
-  if let Ok(bytes) = response.bytes().await {
-    if let Ok(json) = serde_json::from_slice(&bytes) {
-      return Ok(json);
-    }
+  parse_json_slice(&response.bytes().await?)
+}
+
+#[inline(always)]
+pub(crate) fn parse_json_slice<T>(bytes: &[u8]) -> crate::Result<T>
+where
+  T: DeserializeOwned,
+{
+  Ok(serde_json::from_slice(bytes)?)
+}
+
+// like parse_json_slice, but rejecting fields unknown to `T`.
+pub(crate) fn parse_json_slice_strict<T>(bytes: &[u8]) -> crate::Result<T>
+where
+  T: DeserializeOwned,
+{
+  let value = serde_json::from_slice(bytes)?;
+  let (parsed, fields) = strict::from_value(&value)?;
+
+  if fields.is_empty() {
+    Ok(parsed)
+  } else {
+    Err(crate::Error::UnknownFields { fields })
   }
+}
+
+pub(crate) fn get_custom_avatar(hash: &Option<String>, id: u64) -> Option<String> {
+  hash.as_ref().map(|hash| {
+    let ext = if hash.starts_with("a_") { "gif" } else { "png" };
 
-  Err(Error::InternalServerError)
+    format!("https://cdn.discordapp.com/avatars/{id}/{hash}.{ext}?size=1024")
+  })
 }
 
 pub(crate) fn get_avatar(hash: &Option<String>, id: u64) -> String {
-  match hash {
-    Some(hash) => {
-      let ext = if hash.starts_with("a_") { "gif" } else { "png" };
-
-      format!("https://cdn.discordapp.com/avatars/{id}/{hash}.{ext}?size=1024")
-    }
-    _ => format!(
+  get_custom_avatar(hash, id).unwrap_or_else(|| {
+    format!(
       "https://cdn.discordapp.com/embed/avatars/{}.png",
       (id >> 22) % 5
-    ),
-  }
+    )
+  })
 }
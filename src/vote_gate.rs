@@ -0,0 +1,130 @@
+use crate::{Client, Result, Snowflake};
+use core::time::Duration;
+use std::{
+  collections::HashMap,
+  sync::{Arc, Mutex},
+  time::Instant,
+};
+
+/// A helper for vote-gated commands, which remembers whether each user has voted for a short while so that spamming such a command doesn't send a request every time.
+///
+/// Both outcomes are remembered, so a user who votes right after being refused is only let through once the TTL has passed. Keep it short, e.g. a minute.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```rust,no_run
+/// use core::time::Duration;
+/// use topgg::{Client, VoteGate};
+///
+/// # async fn run() -> topgg::Result<()> {
+/// let client = Client::new(std::env::var("TOPGG_TOKEN").unwrap());
+/// let gate = VoteGate::new(client, Duration::from_secs(60));
+///
+/// if gate.check(661200758510977084u64).await? {
+///   println!("Thanks for voting!");
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[must_use]
+#[derive(Debug)]
+pub struct VoteGate {
+  client: Arc<Client>,
+  ttl: Duration,
+  // whether each user has voted, and when it was checked.
+  voted: Mutex<HashMap<u64, (Instant, bool)>>,
+}
+
+impl VoteGate {
+  /// Creates a [`VoteGate`] remembering each user's vote status for `ttl`.
+  pub fn new<C>(client: C, ttl: Duration) -> Self
+  where
+    C: Into<Arc<Client>>,
+  {
+    Self {
+      client: client.into(),
+      ttl,
+      voted: Mutex::new(HashMap::new()),
+    }
+  }
+
+  /// The [`Client`] used for checking votes.
+  #[inline(always)]
+  pub fn client(&self) -> &Client {
+    &self.client
+  }
+
+  /// Checks if the specified user has voted your Discord bot, as [`Client::has_voted`] would, unless it was already checked within the TTL.
+  ///
+  /// # Panics
+  ///
+  /// Panics if any of the following conditions are met:
+  /// - The user ID argument is a string and it's not a valid ID (expected things like `"123456789"`)
+  /// - The client uses an invalid [Top.gg API](https://docs.top.gg) token (unauthorized)
+  ///
+  /// # Errors
+  ///
+  /// Errors if any of the following conditions are met, in which case nothing is remembered:
+  /// - An internal error from the client itself preventing it from sending a HTTP request to [Top.gg](https://top.gg) ([`InternalClientError`][crate::Error::InternalClientError])
+  /// - An unexpected response from the [Top.gg](https://top.gg) servers ([`InternalServerError`][crate::Error::InternalServerError])
+  /// - The client is being ratelimited from sending more HTTP requests ([`Ratelimit`][crate::Error::Ratelimit])
+  pub async fn check<I>(&self, user_id: I) -> Result<bool>
+  where
+    I: Snowflake,
+  {
+    let user_id = user_id.as_snowflake();
+
+    let cached = self
+      .voted
+      .lock()
+      .unwrap()
+      .get(&user_id)
+      .filter(|(at, _)| at.elapsed() < self.ttl)
+      .map(|(_, voted)| *voted);
+
+    if let Some(voted) = cached {
+      return Ok(voted);
+    }
+
+    let voted = self.client.has_voted(user_id).await?;
+    let mut cache = self.voted.lock().unwrap();
+
+    // forget expired statuses so that the map doesn't grow indefinitely.
+    cache.retain(|_, (at, _)| at.elapsed() < self.ttl);
+    cache.insert(user_id, (Instant::now(), voted));
+
+    Ok(voted)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::mock::{MockServer, Response};
+
+  #[tokio::test]
+  async fn statuses_are_remembered_within_the_ttl() {
+    let server = MockServer::start(|request| {
+      if request.path.ends_with("userId=1") {
+        Response::json(r#"{"voted":1}"#)
+      } else {
+        Response::json(r#"{"voted":0}"#)
+      }
+    });
+    let gate = VoteGate::new(server.client(), Duration::from_secs(60));
+
+    assert!(gate.check(1u64).await.unwrap());
+    assert!(gate.check(1u64).await.unwrap());
+    assert!(!gate.check("2").await.unwrap());
+    assert!(!gate.check(2u64).await.unwrap());
+    assert_eq!(server.requests().len(), 2);
+
+    let gate = VoteGate::new(server.client(), Duration::ZERO);
+
+    assert!(gate.check(1u64).await.unwrap());
+    assert!(gate.check(1u64).await.unwrap());
+    assert_eq!(server.requests().len(), 4);
+  }
+}
@@ -0,0 +1,145 @@
+use std::net::IpAddr;
+
+/// A list of IP addresses and networks that webhook requests are allowed to come from, as defense in depth against forged requests.
+///
+/// Fill it with the addresses [Top.gg](https://top.gg) documents as its webhook sources, then pass it to a framework integration, e.g. `topgg::axum::webhook_with_allowlist`.
+///
+/// **NOTE:** The address checked is the one of the connecting peer. Behind a reverse proxy or load balancer, that is the proxy's address, and the original one is only found in headers like `X-Forwarded-For`, which this does not read as they can be forged by anyone reaching your server directly. In that case, filter the source addresses at your proxy instead.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```rust,no_run
+/// use topgg::IpAllowlist;
+///
+/// let allowlist = IpAllowlist::new()
+///   .allow("203.0.113.7".parse().unwrap())
+///   .allow_network("198.51.100.0".parse().unwrap(), 24);
+/// ```
+#[must_use]
+#[derive(Clone, Debug, Default)]
+pub struct IpAllowlist {
+  networks: Vec<(IpAddr, u8)>,
+}
+
+impl IpAllowlist {
+  /// Creates an empty [`IpAllowlist`], which rejects every address.
+  #[inline(always)]
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Allows a single IP address.
+  #[inline(always)]
+  pub fn allow(self, addr: IpAddr) -> Self {
+    let prefix_len = if addr.is_ipv4() { 32 } else { 128 };
+
+    self.allow_network(addr, prefix_len)
+  }
+
+  /// Allows a whole network in CIDR notation, e.g. `198.51.100.0` with a `prefix_len` of `24` for `198.51.100.0/24`. IPv4-mapped IPv6 networks, e.g. `::ffff:198.51.100.0/120`, are stored as their IPv4 counterpart.
+  ///
+  /// # Panics
+  ///
+  /// Panics if `prefix_len` is longer than the address itself (32 bits for IPv4, 128 bits for IPv6), or shorter than the 96 bits of the `::ffff:0:0/96` prefix for IPv4-mapped IPv6 addresses.
+  pub fn allow_network(mut self, addr: IpAddr, prefix_len: u8) -> Self {
+    let canonical = addr.to_canonical();
+
+    let prefix_len = match (addr, canonical) {
+      (IpAddr::V6(_), IpAddr::V4(_)) => prefix_len.checked_sub(96).filter(|len| *len <= 32),
+      (IpAddr::V4(_), _) => Some(prefix_len).filter(|len| *len <= 32),
+      _ => Some(prefix_len).filter(|len| *len <= 128),
+    };
+
+    self.networks.push((
+      canonical,
+      prefix_len.expect("Network prefix length out of range."),
+    ));
+    self
+  }
+
+  /// Whether an IP address is allowed or not. IPv4-mapped IPv6 addresses are treated as their IPv4 counterpart.
+  #[must_use]
+  pub fn contains(&self, addr: IpAddr) -> bool {
+    let addr = addr.to_canonical();
+
+    self
+      .networks
+      .iter()
+      .any(|&(network, prefix_len)| match (network, addr) {
+        (IpAddr::V4(network), IpAddr::V4(addr)) => prefix_matches(
+          network.to_bits().into(),
+          addr.to_bits().into(),
+          32,
+          prefix_len,
+        ),
+        (IpAddr::V6(network), IpAddr::V6(addr)) => {
+          prefix_matches(network.to_bits(), addr.to_bits(), 128, prefix_len)
+        }
+        _ => false,
+      })
+  }
+}
+
+#[inline(always)]
+fn prefix_matches(network: u128, addr: u128, bits: u8, prefix_len: u8) -> bool {
+  let shift = u32::from(bits - prefix_len);
+
+  network.checked_shr(shift).unwrap_or(0) == addr.checked_shr(shift).unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn ip(addr: &str) -> IpAddr {
+    addr.parse().unwrap()
+  }
+
+  #[test]
+  fn allows_listed_addresses_only() {
+    let allowlist = IpAllowlist::new()
+      .allow(ip("203.0.113.7"))
+      .allow_network(ip("198.51.100.0"), 24)
+      .allow_network(ip("2001:db8::"), 32);
+
+    assert!(allowlist.contains(ip("203.0.113.7")));
+    assert!(allowlist.contains(ip("198.51.100.255")));
+    assert!(allowlist.contains(ip("2001:db8::1")));
+    assert!(allowlist.contains(ip("::ffff:203.0.113.7")));
+
+    assert!(!allowlist.contains(ip("203.0.113.8")));
+    assert!(!allowlist.contains(ip("198.51.101.1")));
+    assert!(!allowlist.contains(ip("2001:db9::1")));
+    assert!(!IpAllowlist::new().contains(ip("203.0.113.7")));
+  }
+
+  #[test]
+  fn mapped_networks_are_stored_as_ipv4() {
+    let allowlist = IpAllowlist::new()
+      .allow(ip("::ffff:203.0.113.7"))
+      .allow_network(ip("::ffff:198.51.100.0"), 120);
+
+    assert!(allowlist.contains(ip("203.0.113.7")));
+    assert!(allowlist.contains(ip("198.51.100.42")));
+    assert!(allowlist.contains(ip("::ffff:198.51.100.42")));
+
+    assert!(!allowlist.contains(ip("8.8.8.8")));
+    assert!(!allowlist.contains(ip("203.0.113.8")));
+  }
+
+  #[test]
+  #[should_panic(expected = "Network prefix length out of range.")]
+  fn mapped_networks_need_the_mapped_prefix() {
+    let _ = IpAllowlist::new().allow_network(ip("::ffff:198.51.100.0"), 64);
+  }
+
+  #[test]
+  fn zero_prefix_allows_everything_of_that_family() {
+    let allowlist = IpAllowlist::new().allow_network(ip("0.0.0.0"), 0);
+
+    assert!(allowlist.contains(ip("192.0.2.1")));
+    assert!(!allowlist.contains(ip("2001:db8::1")));
+  }
+}
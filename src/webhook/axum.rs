@@ -1,16 +1,41 @@
-use crate::{webhook::{verify_v1_signature, VoteEvent}, VoteHandler};
+use crate::{
+  webhook::{verify_v1_signature, VoteEvent},
+  IpAllowlist, Vote, VoteHandler,
+};
 use axum::{
-  extract::State,
-  http::{HeaderMap, StatusCode},
+  body::Bytes,
+  extract::{ConnectInfo, FromRequest, Request, State},
+  http::{header, Extensions, HeaderMap, StatusCode},
   response::{IntoResponse, Response},
   routing::post,
   Router,
 };
-use std::sync::Arc;
+use std::{net::SocketAddr, sync::Arc};
 
 struct WebhookState<T> {
   state: Arc<T>,
   password: Arc<String>,
+  allowlist: Option<Arc<IpAllowlist>>,
+}
+
+impl<T> WebhookState<T> {
+  #[inline(always)]
+  fn new(state: Arc<T>, password: String, allowlist: Option<IpAllowlist>) -> Self {
+    Self {
+      state,
+      password: Arc::new(password),
+      allowlist: allowlist.map(Arc::new),
+    }
+  }
+
+  fn is_allowed(&self, extensions: &Extensions) -> bool {
+    match &self.allowlist {
+      Some(allowlist) => extensions
+        .get::<ConnectInfo<SocketAddr>>()
+        .is_some_and(|ConnectInfo(addr)| allowlist.contains(addr.ip())),
+      None => true,
+    }
+  }
 }
 
 impl<T> Clone for WebhookState<T> {
@@ -19,18 +44,24 @@ impl<T> Clone for WebhookState<T> {
     Self {
       state: Arc::clone(&self.state),
       password: Arc::clone(&self.password),
+      allowlist: self.allowlist.clone(),
     }
   }
 }
 
 async fn handler<T>(
   headers: HeaderMap,
+  extensions: Extensions,
   State(webhook): State<WebhookState<T>>,
   body: String,
 ) -> Response
 where
   T: VoteHandler,
 {
+  if !webhook.is_allowed(&extensions) {
+    return (StatusCode::FORBIDDEN, ()).into_response();
+  }
+
   if let Some(authorization) = headers.get("Authorization") {
     if let Ok(authorization) = authorization.to_str() {
       if authorization == *(webhook.password) {
@@ -95,20 +126,36 @@ where
 {
   Router::new()
     .route("/", post(handler::<T>))
-    .with_state(WebhookState {
-      state,
-      password: Arc::new(password),
-    })
+    .with_state(WebhookState::new(state, password, None))
+}
+
+/// Like [`webhook`], but also rejects requests with a 403 (Forbidden) if they don't come from an address in the [`IpAllowlist`].
+///
+/// The peer address is only known if the app is served with [`into_make_service_with_connect_info::<SocketAddr>()`][Router::into_make_service_with_connect_info], otherwise every request is rejected. See [`IpAllowlist`] for the caveats of running behind a reverse proxy.
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(feature = "axum")))]
+pub fn webhook_with_allowlist<T>(password: String, state: Arc<T>, allowlist: IpAllowlist) -> Router
+where
+  T: VoteHandler,
+{
+  Router::new()
+    .route("/", post(handler::<T>))
+    .with_state(WebhookState::new(state, password, Some(allowlist)))
 }
 
 async fn handler_v1<T>(
   headers: HeaderMap,
+  extensions: Extensions,
   State(webhook): State<WebhookState<T>>,
   body: String,
 ) -> Response
 where
   T: VoteHandler,
 {
+  if !webhook.is_allowed(&extensions) {
+    return (StatusCode::FORBIDDEN, ()).into_response();
+  }
+
   let authenticated = headers
     .get("x-topgg-signature")
     .and_then(|signature| signature.to_str().ok())
@@ -147,8 +194,186 @@ where
 {
   Router::new()
     .route("/", post(handler_v1::<T>))
-    .with_state(WebhookState {
-      state,
-      password: Arc::new(secret),
-    })
+    .with_state(WebhookState::new(state, secret, None))
+}
+
+/// Like [`webhook_v1`], but also rejects requests with a 403 (Forbidden) if they don't come from an address in the [`IpAllowlist`].
+///
+/// The same requirements as [`webhook_with_allowlist`] apply.
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(feature = "axum")))]
+pub fn webhook_v1_with_allowlist<T>(secret: String, state: Arc<T>, allowlist: IpAllowlist) -> Router
+where
+  T: VoteHandler,
+{
+  Router::new()
+    .route("/", post(handler_v1::<T>))
+    .with_state(WebhookState::new(state, secret, Some(allowlist)))
+}
+
+/// A [`Vote`] along with the exact raw request body it was parsed from, e.g. for audit logging.
+#[must_use]
+#[derive(Clone, Debug)]
+pub struct RawVote {
+  /// The parsed vote.
+  pub vote: Vote,
+
+  /// The raw request body, byte for byte.
+  pub raw: Bytes,
+}
+
+/// An [`axum`] extractor that represents an **unauthenticated** request containing a [`RawVote`] data.
+///
+/// This is an alternative to [`webhook`] for routes that need to keep the raw payload. To authenticate this structure with a valid password and consume the [`RawVote`] data inside of it, see the [`authenticate`][IncomingRawVote::authenticate] method.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```rust,no_run
+/// use axum::http::StatusCode;
+/// use topgg::axum::IncomingRawVote;
+///
+/// async fn webhook(vote: IncomingRawVote) -> StatusCode {
+///   match vote.authenticate(env!("TOPGG_WEBHOOK_PASSWORD")) {
+///     Some(vote) => {
+///       println!("{:?} ({} bytes)", vote.vote, vote.raw.len());
+///
+///       StatusCode::OK
+///     }
+///     _ => StatusCode::UNAUTHORIZED,
+///   }
+/// }
+/// ```
+#[must_use]
+#[cfg_attr(docsrs, doc(cfg(feature = "axum")))]
+pub struct IncomingRawVote {
+  authorization: Option<String>,
+  vote: RawVote,
+}
+
+impl IncomingRawVote {
+  /// Authenticates a valid password with this request. Returns a [`Some(RawVote)`][`RawVote`] if succeeds, otherwise `None`.
+  #[must_use]
+  #[inline(always)]
+  pub fn authenticate(self, password: &str) -> Option<RawVote> {
+    if self.authorization.as_deref() == Some(password) {
+      Some(self.vote)
+    } else {
+      None
+    }
+  }
+}
+
+impl<S> FromRequest<S> for IncomingRawVote
+where
+  S: Send + Sync,
+{
+  type Rejection = StatusCode;
+
+  async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+    let authorization = req
+      .headers()
+      .get(header::AUTHORIZATION)
+      .and_then(|authorization| authorization.to_str().ok())
+      .map(String::from);
+
+    let raw = Bytes::from_request(req, state)
+      .await
+      .map_err(|_| StatusCode::BAD_REQUEST)?;
+
+    match serde_json::from_slice(&raw) {
+      Ok(vote) => Ok(Self {
+        authorization,
+        vote: RawVote { vote, raw },
+      }),
+      Err(_) => Err(StatusCode::BAD_REQUEST),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use axum::body::Body;
+
+  const BODY: &str = r#"{"bot":"264811613708746752","user":"661200758510977084","type":"upvote","isWeekend":false,"query":"?a=1"}"#;
+
+  fn request(authorization: &str, body: &'static str) -> Request {
+    Request::builder()
+      .method("POST")
+      .header(header::AUTHORIZATION, authorization)
+      .body(Body::from(body))
+      .unwrap()
+  }
+
+  #[derive(Default)]
+  struct Counter(std::sync::atomic::AtomicUsize);
+
+  #[async_trait::async_trait]
+  impl VoteHandler for Counter {
+    async fn voted(&self, _vote: Vote) {
+      self.0.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+    }
+  }
+
+  fn peer(addr: &str) -> Extensions {
+    let mut extensions = Extensions::new();
+    extensions.insert(ConnectInfo::<SocketAddr>(addr.parse().unwrap()));
+    extensions
+  }
+
+  #[tokio::test]
+  async fn allowlist_rejects_unknown_peers() {
+    let counter = Arc::new(Counter::default());
+    let allowlist = IpAllowlist::new().allow("203.0.113.7".parse().unwrap());
+    let webhook = WebhookState::new(
+      Arc::clone(&counter),
+      String::from("password"),
+      Some(allowlist),
+    );
+
+    let mut headers = HeaderMap::new();
+    headers.insert(header::AUTHORIZATION, "password".parse().unwrap());
+
+    for (extensions, status) in [
+      (peer("203.0.113.7:443"), StatusCode::OK),
+      (peer("198.51.100.1:443"), StatusCode::FORBIDDEN),
+      (Extensions::new(), StatusCode::FORBIDDEN),
+    ] {
+      let response = handler(
+        headers.clone(),
+        extensions,
+        State(webhook.clone()),
+        BODY.to_owned(),
+      )
+      .await;
+
+      assert_eq!(response.status(), status);
+    }
+
+    assert_eq!(counter.0.load(std::sync::atomic::Ordering::SeqCst), 1);
+  }
+
+  #[tokio::test]
+  async fn raw_vote_keeps_the_exact_body() {
+    let incoming = IncomingRawVote::from_request(request("password", BODY), &())
+      .await
+      .unwrap();
+    let vote = incoming.authenticate("password").unwrap();
+
+    assert_eq!(vote.raw, BODY.as_bytes());
+    assert_eq!(vote.vote.voter_id, 661200758510977084);
+  }
+
+  #[tokio::test]
+  async fn raw_vote_requires_authentication() {
+    let incoming = IncomingRawVote::from_request(request("wrong", BODY), &())
+      .await
+      .unwrap();
+    assert!(incoming.authenticate("password").is_none());
+
+    let rejection = IncomingRawVote::from_request(request("password", "not json"), &()).await;
+    assert_eq!(rejection.err(), Some(StatusCode::BAD_REQUEST));
+  }
 }
@@ -0,0 +1,94 @@
+use crate::webhook::Vote;
+use std::{
+  collections::{hash_map::Entry, HashMap},
+  sync::Mutex,
+  time::{Duration, Instant},
+};
+
+/// A helper that remembers recently received [`Vote`]s in memory, letting you ignore duplicate webhook deliveries.
+///
+/// [Top.gg](https://top.gg) may retry a webhook delivery it deems failed, which could otherwise grant a voter their rewards twice. A vote is considered a duplicate if the same user voted for the same bot/server within the time window given to [`VoteDedup::new`]. As users can only vote once every 12 hours, any window shorter than that is safe.
+///
+/// This is also the only protection against replayed legacy webhook requests, as those carry no timestamp. v1 webhooks are signed along with one, which [`verify_v1_signature_within`][crate::verify_v1_signature_within] checks against a configurable window: remember votes for at least as long.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```rust,no_run
+/// use std::time::Duration;
+/// use topgg::{Vote, VoteDedup};
+///
+/// let dedup = VoteDedup::new(Duration::from_secs(3600));
+///
+/// fn on_vote(dedup: &VoteDedup, vote: Vote) {
+///   if dedup.is_duplicate(&vote) {
+///     return;
+///   }
+///
+///   // grant rewards...
+/// }
+/// ```
+#[derive(Debug)]
+pub struct VoteDedup {
+  ttl: Duration,
+  seen: Mutex<HashMap<(u64, u64), Instant>>,
+}
+
+impl VoteDedup {
+  /// Creates a new [`VoteDedup`] that remembers each vote for the duration of `ttl`.
+  #[inline(always)]
+  pub fn new(ttl: Duration) -> Self {
+    Self {
+      ttl,
+      seen: Mutex::new(HashMap::new()),
+    }
+  }
+
+  /// Returns `true` if this vote was already seen within the time window, otherwise remembers it and returns `false`.
+  #[must_use]
+  pub fn is_duplicate(&self, vote: &Vote) -> bool {
+    let now = Instant::now();
+    let mut seen = self.seen.lock().unwrap();
+
+    // forget expired votes so that the map doesn't grow indefinitely.
+    seen.retain(|_, at| now.duration_since(*at) < self.ttl);
+
+    match seen.entry((vote.receiver_id, vote.voter_id)) {
+      Entry::Occupied(_) => true,
+      Entry::Vacant(entry) => {
+        entry.insert(now);
+        false
+      }
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn vote(voter_id: &str) -> Vote {
+    serde_json::from_str(&format!(
+      r#"{{"bot":"264811613708746752","user":"{voter_id}","type":"upvote"}}"#
+    ))
+    .unwrap()
+  }
+
+  #[test]
+  fn same_vote_within_window_is_duplicate() {
+    let dedup = VoteDedup::new(Duration::from_secs(3600));
+
+    assert!(!dedup.is_duplicate(&vote("1")));
+    assert!(dedup.is_duplicate(&vote("1")));
+    assert!(!dedup.is_duplicate(&vote("2")));
+  }
+
+  #[test]
+  fn expired_votes_are_forgotten() {
+    let dedup = VoteDedup::new(Duration::ZERO);
+
+    assert!(!dedup.is_duplicate(&vote("1")));
+    assert!(!dedup.is_duplicate(&vote("1")));
+  }
+}
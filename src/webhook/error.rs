@@ -0,0 +1,32 @@
+use core::fmt;
+use std::error;
+
+/// An error coming from handling a [Top.gg](https://top.gg) webhook request. (See [`parse_vote`][crate::parse_vote])
+#[derive(Debug)]
+pub enum WebhookError {
+  /// The request body is not a valid vote payload, e.g. after [Top.gg](https://top.gg) changed its format unexpectedly.
+  Parse {
+    /// The underlying deserialization error.
+    source: serde_json::Error,
+
+    /// The offending request body, kept as-is for logging and investigation.
+    raw: Vec<u8>,
+  },
+}
+
+impl fmt::Display for WebhookError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Self::Parse { source, .. } => write!(f, "failed to parse the webhook payload: {source}"),
+    }
+  }
+}
+
+impl error::Error for WebhookError {
+  #[inline(always)]
+  fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+    match self {
+      Self::Parse { source, .. } => Some(source),
+    }
+  }
+}
@@ -0,0 +1,122 @@
+use crate::webhook::{parse_vote, verify_v1_signature, Vote, VoteEvent};
+use http_body_util::{BodyExt, Limited};
+use hyper::{body::Body, Request};
+use std::error::Error;
+
+/// The largest request body [`Vote::from_hyper_request`] reads, in bytes. Vote payloads are well below a kilobyte.
+pub const MAX_HYPER_BODY_SIZE: usize = 8 * 1024;
+
+impl Vote {
+  /// Reads and authenticates a [`Vote`] from a raw [`hyper`] request, for servers that don't use any of the supported web frameworks. Returns [`None`] if the request is unauthorized, or its body can't be read or parsed.
+  ///
+  /// Unlike most web frameworks, [`hyper`] doesn't limit request bodies by itself. The headers are checked before reading anything, and bodies longer than [`MAX_HYPER_BODY_SIZE`] are rejected without being buffered whole.
+  ///
+  /// Requests carrying an `x-topgg-signature` header are v1 webhooks, verified against `secret` as the `whs_`-prefixed webhook secret generated by [Top.gg](https://top.gg). Other requests are legacy webhooks, whose `Authorization` header must equal `secret` as the webhook password.
+  ///
+  /// # Examples
+  ///
+  /// Basic usage:
+  ///
+  /// ```rust,no_run
+  /// use hyper::{body::Incoming, Request};
+  /// use topgg::Vote;
+  ///
+  /// async fn webhook(request: Request<Incoming>) {
+  ///   let password = std::env::var("TOPGG_WEBHOOK_PASSWORD").unwrap();
+  ///
+  ///   match Vote::from_hyper_request(request, &password).await {
+  ///     Some(vote) => {
+  ///       println!("{:?}", vote);
+  ///
+  ///       // respond with 200 OK...
+  ///     }
+  ///     _ => {
+  ///       // respond with 401 UNAUTHORIZED...
+  ///     }
+  ///   }
+  /// }
+  /// ```
+  #[cfg_attr(docsrs, doc(cfg(feature = "hyper")))]
+  pub async fn from_hyper_request<B>(request: Request<B>, secret: &str) -> Option<Self>
+  where
+    B: Body,
+    B::Error: Into<Box<dyn Error + Send + Sync>>,
+  {
+    let (parts, body) = request.into_parts();
+
+    let signature = match parts.headers.get("x-topgg-signature") {
+      Some(signature) => Some(signature.to_str().ok()?),
+      None if parts.headers.get("Authorization")?.to_str().ok()? == secret => None,
+      None => return None,
+    };
+
+    let body = Limited::new(body, MAX_HYPER_BODY_SIZE)
+      .collect()
+      .await
+      .ok()?
+      .to_bytes();
+
+    match signature {
+      Some(signature) if verify_v1_signature(secret, signature, &body) => {
+        serde_json::from_slice::<VoteEvent>(&body).ok()?.into_vote()
+      }
+      Some(_) => None,
+      None => parse_vote(&body).ok(),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use http_body_util::Full;
+  use hyper::body::Bytes;
+
+  const BODY: &str = r#"{"bot":"264811613708746752","user":"1","type":"upvote"}"#;
+
+  fn request(authorization: &str) -> Request<Full<Bytes>> {
+    Request::post("/webhook")
+      .header("Authorization", authorization)
+      .body(Full::new(Bytes::from_static(BODY.as_bytes())))
+      .unwrap()
+  }
+
+  #[tokio::test]
+  async fn authenticates_legacy_requests() {
+    let vote = Vote::from_hyper_request(request("password"), "password")
+      .await
+      .unwrap();
+
+    assert_eq!(vote.receiver_id, 264811613708746752);
+    assert_eq!(vote.voter_id, 1);
+
+    assert!(Vote::from_hyper_request(request("wrong"), "password")
+      .await
+      .is_none());
+  }
+
+  #[tokio::test]
+  async fn rejects_oversized_bodies() {
+    let padded = format!("{}{}", BODY, " ".repeat(MAX_HYPER_BODY_SIZE));
+    let request = Request::post("/webhook")
+      .header("Authorization", "password")
+      .body(Full::new(Bytes::from(padded)))
+      .unwrap();
+
+    assert!(Vote::from_hyper_request(request, "password")
+      .await
+      .is_none());
+  }
+
+  #[tokio::test]
+  async fn rejects_invalid_signatures() {
+    let request = Request::post("/webhook")
+      .header("x-topgg-signature", "t=0,v1=00")
+      .body(Full::new(Bytes::from_static(BODY.as_bytes())))
+      .unwrap();
+
+    assert!(Vote::from_hyper_request(request, "whs_secret")
+      .await
+      .is_none());
+  }
+}
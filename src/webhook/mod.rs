@@ -1,3 +1,7 @@
+mod error;
+#[cfg_attr(docsrs, doc(cfg(feature = "webhook")))]
+pub use error::WebhookError;
+
 mod vote;
 #[cfg_attr(docsrs, doc(cfg(feature = "webhook")))]
 pub use vote::*;
@@ -6,12 +10,26 @@ mod v1;
 #[cfg_attr(docsrs, doc(cfg(feature = "webhook")))]
 pub use v1::*;
 
+mod dedup;
+#[cfg_attr(docsrs, doc(cfg(feature = "webhook")))]
+pub use dedup::VoteDedup;
+
+mod allowlist;
+#[cfg_attr(docsrs, doc(cfg(feature = "webhook")))]
+pub use allowlist::IpAllowlist;
+
 #[cfg(feature = "actix-web")]
 mod actix_web;
 
 #[cfg(feature = "rocket")]
 mod rocket;
 
+#[cfg(feature = "hyper")]
+mod hyper;
+#[cfg(feature = "hyper")]
+#[cfg_attr(docsrs, doc(cfg(feature = "hyper")))]
+pub use hyper::MAX_HYPER_BODY_SIZE;
+
 cfg_if::cfg_if! {
   if #[cfg(feature = "axum")] {
     /// Wrapper for working with the [`axum`](https://crates.io/crates/axum) web framework.
@@ -4,12 +4,13 @@ use serde::Deserialize;
 use sha2::Sha256;
 use std::{
   collections::HashMap,
-  time::{SystemTime, UNIX_EPOCH},
+  time::{Duration, SystemTime, UNIX_EPOCH},
 };
 
 /// Maximum accepted clock difference between the `t=` timestamp in the
-/// signature header and this machine, in seconds. Rejects replayed requests.
-const SIGNATURE_TOLERANCE_SECS: u64 = 300;
+/// signature header and this machine, in seconds, used by
+/// [`verify_v1_signature`]. Rejects replayed requests.
+pub const SIGNATURE_TOLERANCE_SECS: u64 = 300;
 
 /// A dispatched [Top.gg](https://top.gg) v1 webhook event envelope
 /// (`vote.create` / `webhook.test`).
@@ -134,7 +135,31 @@ impl VoteEvent {
 /// webhook secret generated by Top.gg. Comparison is constant-time and the
 /// timestamp must be within [`SIGNATURE_TOLERANCE_SECS`] of the current time.
 #[must_use]
+#[inline(always)]
 pub fn verify_v1_signature(secret: &str, signature_header: &str, raw_body: &[u8]) -> bool {
+  verify_v1_signature_within(
+    secret,
+    signature_header,
+    raw_body,
+    Duration::from_secs(SIGNATURE_TOLERANCE_SECS),
+  )
+}
+
+/// Like [`verify_v1_signature`], but accepting timestamps within `tolerance`
+/// of the current time instead, e.g. a shorter window against replays or a
+/// longer one for machines with a skewed clock.
+///
+/// The signed timestamp is the only one [Top.gg](https://top.gg) sends, and
+/// legacy webhooks have none at all: pair this with a
+/// [`VoteDedup`][crate::VoteDedup] remembering votes for at least `tolerance`,
+/// so that a delivery replayed within the window isn't rewarded twice either.
+#[must_use]
+pub fn verify_v1_signature_within(
+  secret: &str,
+  signature_header: &str,
+  raw_body: &[u8],
+  tolerance: Duration,
+) -> bool {
   let mut timestamp: Option<&str> = None;
   let mut signature: Option<&str> = None;
 
@@ -159,7 +184,7 @@ pub fn verify_v1_signature(secret: &str, signature_header: &str, raw_body: &[u8]
     .map(|d| d.as_secs())
     .unwrap_or(0);
 
-  if now.abs_diff(timestamp_secs) > SIGNATURE_TOLERANCE_SECS {
+  if now.abs_diff(timestamp_secs) > tolerance.as_secs() {
     return false;
   }
 
@@ -220,6 +245,28 @@ mod tests {
     assert!(!verify_v1_signature("whs_test", "t=abc,v1=zz", body.as_bytes()));
   }
 
+  #[test]
+  fn tolerance_is_configurable() {
+    let body = "{}";
+    let stale = sign("whs_test", now() - 600, body);
+    assert!(!verify_v1_signature("whs_test", &stale, body.as_bytes()));
+    assert!(verify_v1_signature_within(
+      "whs_test",
+      &stale,
+      body.as_bytes(),
+      Duration::from_secs(3600)
+    ));
+
+    let recent = sign("whs_test", now() - 120, body);
+    assert!(verify_v1_signature("whs_test", &recent, body.as_bytes()));
+    assert!(!verify_v1_signature_within(
+      "whs_test",
+      &recent,
+      body.as_bytes(),
+      Duration::from_secs(60)
+    ));
+  }
+
   #[test]
   fn converts_vote_create_into_vote() {
     let event: VoteEvent = serde_json::from_str(
@@ -1,4 +1,4 @@
-use crate::snowflake;
+use crate::{snowflake, webhook::WebhookError};
 use serde::{Deserialize, Deserializer};
 use std::collections::HashMap;
 
@@ -83,6 +83,46 @@ pub struct Vote {
   pub query: HashMap<String, String>,
 }
 
+impl Vote {
+  /// Whether the weekend multiplier is active or not, meaning a single vote counts as two. Always `false` for server votes.
+  #[must_use]
+  #[inline(always)]
+  pub const fn is_weekend(&self) -> bool {
+    self.is_weekend
+  }
+}
+
+/// Parses a legacy webhook request body into a [`Vote`], for servers that read the body themselves.
+///
+/// **NOTE:** This doesn't authenticate the request in any way. Check its `Authorization` header against your webhook password first.
+///
+/// # Errors
+///
+/// Errors with [`Parse`][crate::WebhookError::Parse] if the body is not a valid vote payload, which keeps the body for investigating format changes.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```rust,no_run
+/// use topgg::WebhookError;
+///
+/// fn on_request(body: &[u8]) {
+///   match topgg::parse_vote(body) {
+///     Ok(vote) => println!("{:?}", vote),
+///     Err(WebhookError::Parse { source, raw }) => {
+///       eprintln!("{source}: {}", String::from_utf8_lossy(&raw));
+///     }
+///   }
+/// }
+/// ```
+pub fn parse_vote(raw: &[u8]) -> Result<Vote, WebhookError> {
+  serde_json::from_slice(raw).map_err(|source| WebhookError::Parse {
+    source,
+    raw: raw.to_vec(),
+  })
+}
+
 cfg_if::cfg_if! {
   if #[cfg(any(feature = "actix-web", feature = "rocket"))] {
     /// A struct that represents an **unauthenticated** request containing a [`Vote`] data.
@@ -149,3 +189,40 @@ cfg_if::cfg_if! {
     }
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn parse_failures_keep_the_raw_payload() {
+    let raw = br#"{"bot":"264811613708746752","voter":{"id":"1"},"type":"upvote"}"#;
+
+    match parse_vote(raw) {
+      Err(WebhookError::Parse { source, raw: kept }) => {
+        assert_eq!(kept, raw);
+        assert!(source.to_string().contains("user"));
+      }
+
+      Ok(vote) => panic!("parsed a malformed payload: {vote:?}"),
+    }
+
+    let vote = parse_vote(br#"{"bot":"264811613708746752","user":"1","type":"test"}"#).unwrap();
+    assert_eq!(vote.voter_id, 1);
+    assert!(vote.is_test);
+  }
+
+  #[test]
+  fn is_weekend() {
+    let vote = |extra: &str| -> Vote {
+      serde_json::from_str(&format!(
+        r#"{{"bot":"264811613708746752","user":"1","type":"upvote"{extra}}}"#
+      ))
+      .unwrap()
+    };
+
+    assert!(vote(r#","isWeekend":true"#).is_weekend());
+    assert!(!vote(r#","isWeekend":false"#).is_weekend());
+    assert!(!vote("").is_weekend());
+  }
+}
@@ -1,6 +1,6 @@
-use crate::{Vote, VoteHandler};
-use std::sync::Arc;
-use warp::{body, header, http::StatusCode, path, Filter, Rejection, Reply};
+use crate::{IpAllowlist, Vote, VoteHandler};
+use std::{net::SocketAddr, sync::Arc};
+use warp::{addr, body, header, http::StatusCode, path, Filter, Rejection, Reply};
 
 /// Creates a new `warp` [`Filter`] for adding an on-vote event handler to your application logic.
 ///
@@ -40,12 +40,42 @@ use warp::{body, header, http::StatusCode, path, Filter, Rejection, Reply};
 ///   warp::serve(routes).run(addr).await
 /// }
 /// ```
+#[inline(always)]
 #[cfg_attr(docsrs, doc(cfg(feature = "warp")))]
 pub fn webhook<T>(
   endpoint: &'static str,
   password: String,
   state: Arc<T>,
 ) -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone
+where
+  T: VoteHandler,
+{
+  filter(endpoint, password, state, None)
+}
+
+/// Like [`webhook`], but also rejects requests with a 403 (Forbidden) if they don't come from an address in the [`IpAllowlist`].
+///
+/// The peer address is only known when served with [`warp::serve`], otherwise every request is rejected. See [`IpAllowlist`] for the caveats of running behind a reverse proxy.
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(feature = "warp")))]
+pub fn webhook_with_allowlist<T>(
+  endpoint: &'static str,
+  password: String,
+  state: Arc<T>,
+  allowlist: IpAllowlist,
+) -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone
+where
+  T: VoteHandler,
+{
+  filter(endpoint, password, state, Some(Arc::new(allowlist)))
+}
+
+fn filter<T>(
+  endpoint: &'static str,
+  password: String,
+  state: Arc<T>,
+  allowlist: Option<Arc<IpAllowlist>>,
+) -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone
 where
   T: VoteHandler,
 {
@@ -53,20 +83,76 @@ where
 
   warp::post()
     .and(path(endpoint))
+    .and(addr::remote())
     .and(header("Authorization"))
     .and(body::json())
-    .then(move |auth: String, vote: Vote| {
-      let current_state = Arc::clone(&state);
-      let current_password = Arc::clone(&password);
+    .then(
+      move |remote: Option<SocketAddr>, auth: String, vote: Vote| {
+        let current_state = Arc::clone(&state);
+        let current_password = Arc::clone(&password);
+        let is_allowed = allowlist
+          .as_ref()
+          .is_none_or(|allowlist| remote.is_some_and(|remote| allowlist.contains(remote.ip())));
 
-      async move {
-        if auth == *current_password {
-          current_state.voted(vote).await;
+        async move {
+          if !is_allowed {
+            StatusCode::FORBIDDEN
+          } else if auth == *current_password {
+            current_state.voted(vote).await;
 
-          StatusCode::OK
-        } else {
-          StatusCode::UNAUTHORIZED
+            StatusCode::OK
+          } else {
+            StatusCode::UNAUTHORIZED
+          }
         }
-      }
-    })
+      },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use std::sync::atomic::{AtomicUsize, Ordering};
+
+  const BODY: &str = r#"{"bot":"264811613708746752","user":"661200758510977084","type":"upvote"}"#;
+
+  #[derive(Default)]
+  struct Counter(AtomicUsize);
+
+  #[async_trait::async_trait]
+  impl VoteHandler for Counter {
+    async fn voted(&self, _vote: Vote) {
+      self.0.fetch_add(1, Ordering::SeqCst);
+    }
+  }
+
+  #[tokio::test]
+  async fn allowlist_rejects_unknown_peers() {
+    let counter = Arc::new(Counter::default());
+    let allowlist = IpAllowlist::new().allow("203.0.113.7".parse().unwrap());
+    let webhook = webhook_with_allowlist(
+      "webhook",
+      String::from("password"),
+      Arc::clone(&counter),
+      allowlist,
+    );
+
+    for (remote, status) in [
+      ("203.0.113.7:443", StatusCode::OK),
+      ("198.51.100.1:443", StatusCode::FORBIDDEN),
+    ] {
+      let response = warp::test::request()
+        .method("POST")
+        .path("/webhook")
+        .remote_addr(remote.parse().unwrap())
+        .header("Authorization", "password")
+        .body(BODY)
+        .reply(&webhook)
+        .await;
+
+      assert_eq!(response.status(), status);
+    }
+
+    assert_eq!(counter.0.load(Ordering::SeqCst), 1);
+  }
 }
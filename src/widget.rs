@@ -0,0 +1,234 @@
+use crate::{Error, Result, Snowflake};
+
+/// The image format of a [Top.gg](https://top.gg) widget.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum WidgetFormat {
+  /// An SVG vector image. This is the default.
+  #[default]
+  Svg,
+
+  /// A PNG raster image, for embedders that don't support SVG.
+  Png,
+}
+
+impl WidgetFormat {
+  #[inline(always)]
+  const fn extension(self) -> &'static str {
+    match self {
+      Self::Svg => "svg",
+      Self::Png => "png",
+    }
+  }
+}
+
+/// Creates the URL of a Discord bot's [Top.gg](https://top.gg) widget, showing its server count, votes and more.
+///
+/// # Panics
+///
+/// Panics if the ID argument is a string but not numeric.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```rust,no_run
+/// use topgg::widget::{self, WidgetFormat};
+///
+/// let _url = widget::widget_url(264811613708746752u64, WidgetFormat::Png);
+/// ```
+#[must_use]
+#[inline(always)]
+pub fn widget_url<I>(bot_id: I, format: WidgetFormat) -> String
+where
+  I: Snowflake,
+{
+  format!(
+    "https://top.gg/api/widget/{}.{}",
+    bot_id.as_snowflake(),
+    format.extension()
+  )
+}
+
+/// The colors of a [Top.gg](https://top.gg) widget, validated and normalized to 6 lowercase hex digits. (See [`widget_url_with_options`])
+#[must_use]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct WidgetOptions {
+  // the query parameter and value of every color set.
+  colors: Vec<(&'static str, String)>,
+}
+
+impl WidgetOptions {
+  /// Creates a [`WidgetOptionsBuilder`], with every color left to [Top.gg](https://top.gg)'s default.
+  #[inline(always)]
+  pub fn builder() -> WidgetOptionsBuilder {
+    WidgetOptionsBuilder::default()
+  }
+}
+
+/// A builder for [`WidgetOptions`], whose colors are validated once [built][WidgetOptionsBuilder::build].
+///
+/// Every color is a 6-digit hex color, optionally prefixed with `#`, e.g. `"#ff0000"` or `"FF0000"`.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```rust,no_run
+/// use topgg::widget::{self, WidgetFormat, WidgetOptions};
+///
+/// let options = WidgetOptions::builder()
+///   .top_color("#ff0000")
+///   .label_color("FFFFFF")
+///   .build()
+///   .unwrap();
+///
+/// let _url = widget::widget_url_with_options(264811613708746752u64, WidgetFormat::Png, &options);
+/// ```
+#[must_use]
+#[derive(Clone, Debug, Default)]
+pub struct WidgetOptionsBuilder {
+  colors: Vec<(&'static str, String)>,
+}
+
+macro_rules! color_setters {
+  ($($(#[$attr:meta])* $name:ident => $param:literal),+) => {$(
+    $(#[$attr])*
+    #[inline(always)]
+    pub fn $name(self, color: &str) -> Self {
+      self.color($param, color)
+    }
+  )+};
+}
+
+impl WidgetOptionsBuilder {
+  color_setters! {
+    /// Sets the color of the widget's top part.
+    top_color => "topcolor",
+
+    /// Sets the color of the widget's middle part.
+    middle_color => "middlecolor",
+
+    /// Sets the color of the bot's username.
+    username_color => "usernamecolor",
+
+    /// Sets the color of the certified badge.
+    certified_color => "certifiedcolor",
+
+    /// Sets the color of the statistics' values.
+    data_color => "datacolor",
+
+    /// Sets the color of the statistics' labels.
+    label_color => "labelcolor",
+
+    /// Sets the color of the highlights.
+    highlight_color => "highlightcolor"
+  }
+
+  // sets a color, replacing any previous one of the same parameter.
+  fn color(mut self, param: &'static str, color: &str) -> Self {
+    self.colors.retain(|(existing, _)| *existing != param);
+    self.colors.push((param, color.to_owned()));
+    self
+  }
+
+  /// Validates every color and creates the [`WidgetOptions`].
+  ///
+  /// # Errors
+  ///
+  /// Errors with [`InvalidColor`][crate::Error::InvalidColor] if any color is not a 6-digit hex color, optionally prefixed with `#`.
+  pub fn build(self) -> Result<WidgetOptions> {
+    let colors = self
+      .colors
+      .into_iter()
+      .map(|(param, color)| {
+        let digits = color.strip_prefix('#').unwrap_or(&color);
+
+        if digits.len() == 6 && digits.bytes().all(|b| b.is_ascii_hexdigit()) {
+          Ok((param, digits.to_ascii_lowercase()))
+        } else {
+          Err(Error::InvalidColor { color })
+        }
+      })
+      .collect::<Result<_>>()?;
+
+    Ok(WidgetOptions { colors })
+  }
+}
+
+/// Creates the URL of a Discord bot's [Top.gg](https://top.gg) widget with custom colors. (See [`widget_url`])
+///
+/// # Panics
+///
+/// Panics if the ID argument is a string but not numeric.
+#[must_use]
+pub fn widget_url_with_options<I>(
+  bot_id: I,
+  format: WidgetFormat,
+  options: &WidgetOptions,
+) -> String
+where
+  I: Snowflake,
+{
+  let mut url = widget_url(bot_id, format);
+
+  for (i, (param, color)) in options.colors.iter().enumerate() {
+    url.push(if i == 0 { '?' } else { '&' });
+    url.push_str(param);
+    url.push('=');
+    url.push_str(color);
+  }
+
+  url
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn widget_urls() {
+    assert_eq!(
+      widget_url(264811613708746752u64, WidgetFormat::default()),
+      "https://top.gg/api/widget/264811613708746752.svg"
+    );
+    assert_eq!(
+      widget_url("264811613708746752", WidgetFormat::Png),
+      "https://top.gg/api/widget/264811613708746752.png"
+    );
+  }
+
+  #[test]
+  fn widget_colors_are_normalized() {
+    let options = WidgetOptions::builder()
+      .top_color("#FF0000")
+      .label_color("00ff00")
+      .top_color("#0000Ff")
+      .build()
+      .unwrap();
+
+    assert_eq!(
+      widget_url_with_options(264811613708746752u64, WidgetFormat::Svg, &options),
+      "https://top.gg/api/widget/264811613708746752.svg?labelcolor=00ff00&topcolor=0000ff"
+    );
+    assert_eq!(
+      widget_url_with_options(
+        264811613708746752u64,
+        WidgetFormat::Png,
+        &WidgetOptions::default()
+      ),
+      "https://top.gg/api/widget/264811613708746752.png"
+    );
+  }
+
+  #[test]
+  fn invalid_widget_colors_are_rejected() {
+    for color in [
+      "", "#", "red", "#ff000", "ff00000", "##ff0000", "#gg0000", "&x=1#",
+    ] {
+      assert!(matches!(
+        WidgetOptions::builder().data_color(color).build(),
+        Err(Error::InvalidColor { color: rejected }) if rejected == color
+      ));
+    }
+  }
+}